@@ -0,0 +1,8 @@
+//! Acceleration structures for spatial queries against flattened geometry
+
+mod spatial_hash;
+
+pub use spatial_hash::{
+    SpatialHash,
+    TriangleRef
+};
@@ -0,0 +1,117 @@
+use crate::common::{
+    MeshDef,
+    PolyMeshError,
+    transform::PolyVector
+};
+use std::collections::HashMap;
+
+/// Identifies one triangle in the list of meshes a `SpatialHash` was built from
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct TriangleRef {
+
+    /// Index of the mesh in the list
+    pub mesh: usize,
+
+    /// Index of the triangle in the mesh's `triangles`
+    pub triangle: usize
+
+}
+
+/// A uniform grid of cubic cells, each listing the triangles whose bounding boxes touch it.
+///
+/// This is a lighter alternative to a tree for scenes with a roughly even density of triangles. Queries are
+/// conservative: they return every triangle that might match, and callers test the candidates against the real geometry
+#[derive(Debug, Clone)]
+pub struct SpatialHash {
+
+    /// Edge length of every cell
+    cell_size: f32,
+
+    /// Triangles touching each occupied cell
+    cells: HashMap<[i64; 3], Vec<TriangleRef>>
+
+}
+
+impl SpatialHash {
+
+    /// Bucket every triangle in `geometry` into cells of `cell_size`.
+    ///
+    /// Fails with `PolyMeshError::InvalidDimensions` if `cell_size` is not a positive number
+    pub fn build(geometry: &[MeshDef], cell_size: f32) -> Result<Self, PolyMeshError> {
+        if !cell_size.is_finite() || cell_size <= 0.0 {
+            return Err(PolyMeshError::InvalidDimensions(format!("Cell size must be positive, got {}", cell_size)));
+        }
+        let mut output = Self {
+            cell_size,
+            cells: HashMap::new()
+        };
+
+        // Add each triangle to every cell its bounding box overlaps
+        for (mesh_index, mesh) in geometry.iter().enumerate() {
+            for (triangle_index, [a, b, c]) in mesh.triangles.iter().flatten().enumerate() {
+                let min = output.cell_of(PolyVector::component_min(*a, PolyVector::component_min(*b, *c)));
+                let max = output.cell_of(PolyVector::component_max(*a, PolyVector::component_max(*b, *c)));
+                let triangle = TriangleRef { mesh: mesh_index, triangle: triangle_index };
+                for cell in cells_between(min, max) {
+                    output.cells.entry(cell).or_default().push(triangle);
+                }
+            }
+        }
+        Ok(output)
+    }
+
+    /// Get the edge length of every cell
+    pub fn cell_size(&self) -> f32 {
+        self.cell_size
+    }
+
+    /// Get the triangles that might contain `point`
+    pub fn query_point(&self, point: PolyVector) -> Vec<TriangleRef> {
+        self.cells.get(&self.cell_of(point)).cloned().unwrap_or_default()
+    }
+
+    /// Get the triangles that might overlap the box from `min` to `max`, sorted and without duplicates
+    pub fn query_aabb(&self, min: PolyVector, max: PolyVector) -> Vec<TriangleRef> {
+        self.collect(cells_between(self.cell_of(min), self.cell_of(max)))
+    }
+
+    /// Get the triangles that might overlap the sphere of `radius` around `center`, sorted and without duplicates.
+    ///
+    /// Cells inside the sphere's bounding box but outside the sphere itself are skipped
+    pub fn query_sphere(&self, center: PolyVector, radius: f32) -> Vec<TriangleRef> {
+        let offset = PolyVector::unit() * radius;
+        let cells = cells_between(self.cell_of(center - offset), self.cell_of(center + offset)).filter(|cell| {
+
+            // Find the closest point in the cell to the center
+            let cell_min = PolyVector { x: cell[0] as f32, y: cell[1] as f32, z: cell[2] as f32 } * self.cell_size;
+            let cell_max = cell_min + PolyVector::unit() * self.cell_size;
+            let closest = PolyVector::component_max(cell_min, PolyVector::component_min(cell_max, center));
+            let distance = closest - center;
+            distance.dot(distance) <= radius * radius
+        });
+        self.collect(cells)
+    }
+
+    /// Get the cell that `point` falls in
+    fn cell_of(&self, point: PolyVector) -> [i64; 3] {
+        [
+            (point.x / self.cell_size).floor() as i64,
+            (point.y / self.cell_size).floor() as i64,
+            (point.z / self.cell_size).floor() as i64
+        ]
+    }
+
+    /// Gather the triangles in every one of `cells`, sorted and without duplicates
+    fn collect(&self, cells: impl Iterator<Item = [i64; 3]>) -> Vec<TriangleRef> {
+        let mut output: Vec<TriangleRef> = cells.filter_map(|cell| self.cells.get(&cell)).flatten().copied().collect();
+        output.sort_unstable();
+        output.dedup();
+        output
+    }
+
+}
+
+/// Iterate every cell in the box of cells from `min` to `max`, inclusive
+fn cells_between(min: [i64; 3], max: [i64; 3]) -> impl Iterator<Item = [i64; 3]> {
+    (min[0]..=max[0]).flat_map(move |x| (min[1]..=max[1]).flat_map(move |y| (min[2]..=max[2]).map(move |z| [x, y, z])))
+}
@@ -0,0 +1,103 @@
+//! Utilities for animating between PolyMesh states
+
+use crate::common::{
+    PolyMesh,
+    MeshDef,
+    PolyMeshError,
+    transform::{
+        PolyVector,
+        PolyQuaternion,
+        PolyTransform
+    }
+};
+
+/// Linearly interpolate between two structurally-identical trees.
+///
+/// Child translations and scales are linearly interpolated (and rotations spherically interpolated) by `t`, where `0.0` is `a` and `1.0` is `b`. Everything else
+/// (names, metadata, colors, geometry) is copied from `a`. An error is returned if the trees do not share a topology.
+pub fn lerp_trees(a: &PolyMesh, b: &PolyMesh, t: f32) -> Result<PolyMesh, PolyMeshError> {
+    lerp_trees_recursive(a, b, t, false, "/")
+}
+
+/// Like `lerp_trees`, but also interpolates the vertex positions of matching geometry
+pub fn lerp_trees_with_geometry(a: &PolyMesh, b: &PolyMesh, t: f32) -> Result<PolyMesh, PolyMeshError> {
+    lerp_trees_recursive(a, b, t, true, "/")
+}
+
+fn lerp_trees_recursive(a: &PolyMesh, b: &PolyMesh, t: f32, lerp_geometry: bool, path: &str) -> Result<PolyMesh, PolyMeshError> {
+
+    // Both meshes must have the same shape
+    if a.mesh_type != b.mesh_type || a.children.len() != b.children.len() {
+        return Err(PolyMeshError::TopologyMismatch(path.to_string()));
+    }
+
+    // Build the output from a copy of the first state
+    let mut output = a.clone();
+
+    // Handle geometry
+    if lerp_geometry {
+        output.geometry = match (&a.geometry, &b.geometry) {
+            (Some(geo_a), Some(geo_b)) => Some(lerp_geometry_def(geo_a, geo_b, t, path)?),
+            (None, None) => None,
+            _ => return Err(PolyMeshError::TopologyMismatch(path.to_string()))
+        };
+    }
+
+    // Handle every child
+    for (i, (child_a, child_b)) in a.children.iter().zip(b.children.iter()).enumerate() {
+
+        // Children must line up by path
+        let child_path = format!("{}{}", path.trim_end_matches('/'), child_a.path);
+        if child_a.path != child_b.path {
+            return Err(PolyMeshError::TopologyMismatch(child_path));
+        }
+
+        // Interpolate the transform
+        let output_child = &mut output.children[i];
+        output_child.transform = lerp_transform(&child_a.transform, &child_b.transform, t);
+
+        // Interpolate the child itself
+        *output_child.mesh = lerp_trees_recursive(&child_a.mesh, &child_b.mesh, t, lerp_geometry, &child_path)?;
+    }
+
+    Ok(output)
+}
+
+fn lerp_transform(a: &PolyTransform, b: &PolyTransform, t: f32) -> PolyTransform {
+    PolyTransform {
+        translation: match (a.translation, b.translation) {
+            (None, None) => None,
+            _ => Some(PolyVector::lerp(a.get_translation(), b.get_translation(), t))
+        },
+        rotation: match (a.rotation, b.rotation) {
+            (None, None) => None,
+            _ => Some(PolyQuaternion::slerp(a.get_rotation(), b.get_rotation(), t))
+        },
+        scale: match (a.scale, b.scale) {
+            (None, None) => None,
+            _ => Some(PolyVector::lerp(a.get_scale(), b.get_scale(), t))
+        },
+        pivot: match (a.pivot, b.pivot) {
+            (None, None) => None,
+            (pivot_a, pivot_b) => Some(PolyVector::lerp(pivot_a.unwrap_or_else(PolyVector::zero), pivot_b.unwrap_or_else(PolyVector::zero), t))
+        }
+    }
+}
+
+fn lerp_geometry_def(a: &MeshDef, b: &MeshDef, t: f32, path: &str) -> Result<MeshDef, PolyMeshError> {
+    let mut output = a.clone();
+
+    output.triangles = match (&a.triangles, &b.triangles) {
+        (Some(tris_a), Some(tris_b)) if tris_a.len() == tris_b.len() => Some(
+            tris_a.iter().zip(tris_b.iter()).map(|(tri_a, tri_b)| [
+                PolyVector::lerp(tri_a[0], tri_b[0], t),
+                PolyVector::lerp(tri_a[1], tri_b[1], t),
+                PolyVector::lerp(tri_a[2], tri_b[2], t)
+            ]).collect()
+        ),
+        (None, None) => None,
+        _ => return Err(PolyMeshError::TopologyMismatch(path.to_string()))
+    };
+
+    Ok(output)
+}
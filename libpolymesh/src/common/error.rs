@@ -0,0 +1,88 @@
+use std::fmt;
+
+/// Errors that can occur while working with PolyMeshes
+#[derive(Debug)]
+pub enum PolyMeshError {
+
+    /// A filesystem operation failed
+    Io(std::io::Error),
+
+    /// A JSON file could not be (de)serialized
+    Json(serde_json::Error),
+
+    /// A `polymeta.json` or `mesh.json` file could not be read or parsed. Holds the path of the file
+    InvalidFile(String, serde_json::Error),
+
+    /// Geometry contains a NaN or infinite vertex. Holds the path of the offending file
+    NonFiniteGeometry(String),
+
+    /// Two trees that were expected to share a structure do not. Holds the path where they diverge
+    TopologyMismatch(String),
+
+    /// A path does not refer to any mesh in the tree. Holds the offending path
+    UnresolvedPath(String),
+
+    /// A reserved metadata key was written directly. Holds the key
+    ReservedMetadataKey(String),
+
+    /// Input data does not match the dimensions it was described with. Holds a description of the mismatch
+    InvalidDimensions(String),
+
+    /// A file contains a field that is not part of the format. Holds the path of the file, and the path of the field within it
+    UnknownField(String, String),
+
+    /// Two meshes cannot be combined because their rendering settings differ. Holds a description of the difference
+    IncompatibleMeshes(String),
+
+    /// A `polymeta.json` lists the same child twice with the same transform. Holds the path of the file, and the path of the child
+    DuplicateChild(String, String),
+
+    /// A transform collapses space (ex: a zero scale), so it can not be inverted. Holds a description of the transform
+    DegenerateTransform(String),
+
+    /// A metadata field that was required is not set. Holds the key
+    MissingMetadata(String),
+
+    /// A file is not valid UTF-8 text. Holds the path of the file
+    Encoding(String),
+
+    /// Two transforms can not be combined into one `PolyTransform` without distorting the result (ex: a rotation under a non-uniform scale). Holds the path where this happened
+    UnrepresentableTransform(String)
+
+}
+
+impl fmt::Display for PolyMeshError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PolyMeshError::Io(error) => write!(f, "IO error: {}", error),
+            PolyMeshError::Json(error) => write!(f, "JSON error: {}", error),
+            PolyMeshError::InvalidFile(path, error) => write!(f, "Could not read {}: {}", path, error),
+            PolyMeshError::NonFiniteGeometry(path) => write!(f, "Non-finite geometry in: {}", path),
+            PolyMeshError::TopologyMismatch(path) => write!(f, "Mesh topology differs at: {}", path),
+            PolyMeshError::UnresolvedPath(path) => write!(f, "Could not resolve path: {}", path),
+            PolyMeshError::ReservedMetadataKey(key) => write!(f, "Metadata key is reserved: {}", key),
+            PolyMeshError::InvalidDimensions(message) => write!(f, "Invalid dimensions: {}", message),
+            PolyMeshError::UnknownField(path, field) => write!(f, "Unknown field {} in: {}", field, path),
+            PolyMeshError::IncompatibleMeshes(message) => write!(f, "Meshes cannot be merged: {}", message),
+            PolyMeshError::DuplicateChild(path, child) => write!(f, "Duplicate child {} in: {}", child, path),
+            PolyMeshError::DegenerateTransform(transform) => write!(f, "Transform can not be inverted: {}", transform),
+            PolyMeshError::MissingMetadata(key) => write!(f, "Metadata field is not set: {}", key),
+            PolyMeshError::Encoding(path) => write!(f, "File is not valid UTF-8: {}", path),
+            PolyMeshError::UnrepresentableTransform(path) => write!(f, "Transforms can not be combined at: {}", path)
+        }
+    }
+}
+
+impl std::error::Error for PolyMeshError {}
+
+impl From<std::io::Error> for PolyMeshError {
+    fn from(error: std::io::Error) -> Self {
+        PolyMeshError::Io(error)
+    }
+}
+
+impl From<serde_json::Error> for PolyMeshError {
+    fn from(error: serde_json::Error) -> Self {
+        PolyMeshError::Json(error)
+    }
+}
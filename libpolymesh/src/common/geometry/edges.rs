@@ -0,0 +1,69 @@
+use crate::common::{
+    MeshDef,
+    transform::PolyVector
+};
+use super::vertex_key;
+use std::collections::HashMap;
+
+impl MeshDef {
+
+    /// Get every distinct vertex position in this mesh, in the order they first appear.
+    ///
+    /// These are what the indices returned by `edges` and `boundary_edges` refer to
+    pub fn unique_vertices(&self) -> Vec<PolyVector> {
+        self.indexed_triangles().0
+    }
+
+    /// Get every unique, undirected edge in this mesh as a pair of indices into `unique_vertices`, with the smaller index first
+    pub fn edges(&self) -> Vec<(u32, u32)> {
+        self.edge_counts().into_iter().map(|(edge, _)| edge).collect()
+    }
+
+    /// Get every edge that belongs to exactly one triangle, as a pair of indices into `unique_vertices`.
+    ///
+    /// These outline any holes or open borders in the mesh
+    pub fn boundary_edges(&self) -> Vec<(u32, u32)> {
+        self.edge_counts().into_iter().filter(|(_, count)| *count == 1).map(|(edge, _)| edge).collect()
+    }
+
+    /// Get the unique vertices of this mesh, along with every triangle as indices into them
+    pub(super) fn indexed_triangles(&self) -> (Vec<PolyVector>, Vec<[u32; 3]>) {
+        let mut vertices = Vec::new();
+        let mut indices = Vec::new();
+        let mut lookup = HashMap::new();
+
+        for triangle in self.triangles.iter().flatten() {
+            let mut indexed = [0; 3];
+            for (index, point) in indexed.iter_mut().zip(triangle.iter()) {
+                *index = *lookup.entry(vertex_key(*point)).or_insert_with(|| {
+                    vertices.push(*point);
+                    vertices.len() as u32 - 1
+                });
+            }
+            indices.push(indexed);
+        }
+
+        (vertices, indices)
+    }
+
+    /// Count how many triangles use each edge, keeping edges in the order they first appear
+    fn edge_counts(&self) -> Vec<((u32, u32), usize)> {
+        let mut counts: Vec<((u32, u32), usize)> = Vec::new();
+        let mut lookup = HashMap::new();
+
+        for triangle in self.indexed_triangles().1 {
+            for i in 0..3 {
+                let (a, b) = (triangle[i], triangle[(i + 1) % 3]);
+                let edge = if a < b { (a, b) } else { (b, a) };
+                let slot = *lookup.entry(edge).or_insert_with(|| {
+                    counts.push((edge, 0));
+                    counts.len() - 1
+                });
+                counts[slot].1 += 1;
+            }
+        }
+
+        counts
+    }
+
+}
@@ -0,0 +1,90 @@
+use crate::common::MeshDef;
+
+/// A per-vertex attribute that `MeshDef::interleaved` can pack into a vertex buffer
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum VertexAttr {
+
+    /// Position as `x, y, z`
+    Position,
+
+    /// Smooth normal as `x, y, z` (see `smooth_normals`)
+    Normal,
+
+    /// Texture coordinates as `u, v`, or zeros if the mesh has none
+    Uv,
+
+    /// The mesh color as `r, g, b, a`, each from 0 to 1
+    Color
+
+}
+
+impl VertexAttr {
+
+    /// Get the number of floats this attribute takes up in each vertex
+    pub fn components(&self) -> usize {
+        match self {
+            VertexAttr::Position | VertexAttr::Normal => 3,
+            VertexAttr::Uv => 2,
+            VertexAttr::Color => 4
+        }
+    }
+
+}
+
+impl MeshDef {
+
+    /// Pack every triangle corner into a single vertex buffer, with attributes in the order given by `layout`.
+    ///
+    /// Positions are always included, and come first if `layout` leaves them out. Each corner becomes its own vertex,
+    /// so the stride is the sum of `VertexAttr::components` over the layout, and `index_buffer_u32` gives the matching indices
+    ///
+    /// ```rust
+    /// use libpolymesh::prelude as pmf;
+    /// use libpolymesh::common::geometry::VertexAttr;
+    ///
+    /// let quad = pmf::make_quad(pmf::PolyVector::unit_y(), pmf::PolyVector::unit(), pmf::PolyVector::zero(), pmf::PolyVector::unit_x(), pmf::PolyColor::green());
+    /// let geometry = quad.geometry.unwrap();
+    /// let buffer = geometry.interleaved(&[VertexAttr::Position, VertexAttr::Normal, VertexAttr::Uv]);
+    /// assert_eq!(buffer.len(), geometry.index_buffer_u32().len() * 8);
+    /// ```
+    pub fn interleaved(&self, layout: &[VertexAttr]) -> Vec<f32> {
+        let mut layout = layout.to_vec();
+        if !layout.contains(&VertexAttr::Position) {
+            layout.insert(0, VertexAttr::Position);
+        }
+
+        // Only compute normals if they are asked for
+        let normals = if layout.contains(&VertexAttr::Normal) { self.smooth_normals() } else { Vec::new() };
+        let color = [self.color.r, self.color.g, self.color.b, self.color.a].map(|channel| channel as f32 / 255.0);
+
+        let stride: usize = layout.iter().map(VertexAttr::components).sum();
+        let mut buffer = Vec::with_capacity(self.triangles.as_ref().map_or(0, Vec::len) * 3 * stride);
+        for (i, triangle) in self.triangles.iter().flatten().enumerate() {
+            for (corner, point) in triangle.iter().enumerate() {
+                for attribute in &layout {
+                    match attribute {
+                        VertexAttr::Position => buffer.extend_from_slice(&[point.x, point.y, point.z]),
+                        VertexAttr::Normal => {
+                            let normal = normals[i][corner];
+                            buffer.extend_from_slice(&[normal.x, normal.y, normal.z]);
+                        },
+                        VertexAttr::Uv => {
+                            let uv = self.uvs.as_ref().and_then(|uvs| uvs.get(i * 3 + corner)).copied().unwrap_or([0.0, 0.0]);
+                            buffer.extend_from_slice(&uv);
+                        },
+                        VertexAttr::Color => buffer.extend_from_slice(&color)
+                    }
+                }
+            }
+        }
+        buffer
+    }
+
+    /// Get the index buffer matching `interleaved`, with three indices per triangle.
+    ///
+    /// Every corner is its own vertex in the interleaved buffer, so this counts up from zero
+    pub fn index_buffer_u32(&self) -> Vec<u32> {
+        (0..self.triangles.as_ref().map_or(0, Vec::len) as u32 * 3).collect()
+    }
+
+}
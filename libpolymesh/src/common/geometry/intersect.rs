@@ -0,0 +1,196 @@
+use crate::common::{
+    MeshDef,
+    transform::PolyVector,
+    serialization::data::mesh::bounds_of_points
+};
+
+/// Distance from a plane below which a vertex is treated as lying on it, as a fraction of the longest edge of the two triangles
+const PLANE_EPSILON: f32 = 1e-6;
+
+type Triangle = [PolyVector; 3];
+
+/// Check if two axis-aligned boxes overlap
+fn boxes_overlap(a: (PolyVector, PolyVector), b: (PolyVector, PolyVector)) -> bool {
+    a.0.x <= b.1.x && b.0.x <= a.1.x && a.0.y <= b.1.y && b.0.y <= a.1.y && a.0.z <= b.1.z && b.0.z <= a.1.z
+}
+
+/// Get the length of the longest edge of either triangle
+fn longest_edge(a: &Triangle, b: &Triangle) -> f32 {
+    a.iter().zip(a.iter().cycle().skip(1)).chain(b.iter().zip(b.iter().cycle().skip(1)))
+        .map(|(start, end)| { let edge = *end - *start; edge.dot(edge).sqrt() })
+        .fold(0.0, f32::max)
+}
+
+/// Get the unit normal of `other`, and the signed distances of every corner of `triangle` from its plane.
+/// Distances shorter than `tolerance` are snapped to zero. A degenerate `other` has a zero normal, putting every corner on its "plane"
+fn plane_distances(triangle: &Triangle, other: &Triangle, tolerance: f32) -> (PolyVector, [f32; 3]) {
+    let normal = (other[1] - other[0]).cross(other[2] - other[0]);
+    let length = normal.dot(normal).sqrt();
+    let normal = if length > 0.0 { normal / length } else { normal };
+    let d = -normal.dot(other[0]);
+    let mut distances = [0.0; 3];
+    for (distance, point) in distances.iter_mut().zip(triangle.iter()) {
+        let value = normal.dot(*point) + d;
+        *distance = if value.abs() < tolerance { 0.0 } else { value };
+    }
+    (normal, distances)
+}
+
+/// Get the interval where a triangle crosses the line its plane shares with another triangle's plane.
+///
+/// `projected` holds each corner projected onto that line, and `distances` holds each corner's distance from the other plane
+fn crossing_interval(projected: [f32; 3], distances: [f32; 3]) -> (f32, f32) {
+
+    // Find the corner that sits alone on its side of the plane
+    let lone = if distances[0] * distances[1] > 0.0 {
+        2
+    } else if distances[0] * distances[2] > 0.0 {
+        1
+    } else if distances[1] * distances[2] > 0.0 || distances[0] != 0.0 {
+        0
+    } else if distances[1] != 0.0 {
+        1
+    } else {
+        2
+    };
+    let (i, j) = ((lone + 1) % 3, (lone + 2) % 3);
+
+    // Find where the two edges leaving that corner cross the plane
+    let along = |other: usize| projected[lone] + (projected[other] - projected[lone]) * distances[lone] / (distances[lone] - distances[other]);
+    let (a, b) = (along(i), along(j));
+    (a.min(b), a.max(b))
+}
+
+/// Check if two triangles that share a plane overlap, by testing their outlines in 2D
+fn coplanar_triangles_intersect(a: &Triangle, b: &Triangle, normal: PolyVector) -> bool {
+
+    // Drop the axis the plane faces the most
+    let (nx, ny, nz) = (normal.x.abs(), normal.y.abs(), normal.z.abs());
+    let flatten = |point: PolyVector| if nx >= ny && nx >= nz {
+        (point.y, point.z)
+    } else if ny >= nz {
+        (point.x, point.z)
+    } else {
+        (point.x, point.y)
+    };
+    let a2: Vec<(f32, f32)> = a.iter().map(|point| flatten(*point)).collect();
+    let b2: Vec<(f32, f32)> = b.iter().map(|point| flatten(*point)).collect();
+
+    let orient = |p: (f32, f32), q: (f32, f32), r: (f32, f32)| (q.0 - p.0) * (r.1 - p.1) - (q.1 - p.1) * (r.0 - p.0);
+
+    // Check every pair of edges
+    for i in 0..3 {
+        let (p1, p2) = (a2[i], a2[(i + 1) % 3]);
+        for j in 0..3 {
+            let (q1, q2) = (b2[j], b2[(j + 1) % 3]);
+            let (d1, d2) = (orient(q1, q2, p1), orient(q1, q2, p2));
+            let (d3, d4) = (orient(p1, p2, q1), orient(p1, p2, q2));
+            if d1 * d2 <= 0.0 && d3 * d4 <= 0.0 && (d1 != d2 || d3 != d4) {
+                return true;
+            }
+        }
+    }
+
+    // With no crossing edges, the triangles only overlap if one is inside the other
+    let contains = |triangle: &[(f32, f32)], point: (f32, f32)| {
+        let signs = [orient(triangle[0], triangle[1], point), orient(triangle[1], triangle[2], point), orient(triangle[2], triangle[0], point)];
+        signs.iter().all(|sign| *sign >= 0.0) || signs.iter().all(|sign| *sign <= 0.0)
+    };
+    contains(&b2, a2[0]) || contains(&a2, b2[0])
+}
+
+/// Möller's triangle-triangle overlap test
+fn triangles_intersect(a: &Triangle, b: &Triangle) -> bool {
+
+    // Reject if either triangle sits entirely on one side of the other's plane
+    let tolerance = PLANE_EPSILON * longest_edge(a, b);
+    let (normal_b, distances_a) = plane_distances(a, b, tolerance);
+    if distances_a.iter().all(|d| *d > 0.0) || distances_a.iter().all(|d| *d < 0.0) {
+        return false;
+    }
+    let (normal_a, distances_b) = plane_distances(b, a, tolerance);
+    if distances_b.iter().all(|d| *d > 0.0) || distances_b.iter().all(|d| *d < 0.0) {
+        return false;
+    }
+
+    // Triangles in the same plane need a 2D test
+    if distances_a.iter().all(|d| *d == 0.0) {
+        return coplanar_triangles_intersect(a, b, normal_a);
+    }
+
+    // Project both triangles onto the line where their planes meet, using its largest axis
+    let direction = normal_a.cross(normal_b);
+    let (dx, dy, dz) = (direction.x.abs(), direction.y.abs(), direction.z.abs());
+    let project = |point: PolyVector| if dx >= dy && dx >= dz { point.x } else if dy >= dz { point.y } else { point.z };
+
+    // The triangles touch if their intervals along that line overlap
+    let interval_a = crossing_interval([project(a[0]), project(a[1]), project(a[2])], distances_a);
+    let interval_b = crossing_interval([project(b[0]), project(b[1]), project(b[2])], distances_b);
+    interval_a.0 <= interval_b.1 && interval_b.0 <= interval_a.1
+}
+
+impl MeshDef {
+
+    /// Check if any triangle of this mesh touches or passes through any triangle of `other`.
+    ///
+    /// Both meshes must be in the same space, so compare flattened (world-space) geometry when checking parts of a scene.
+    /// Meshes and triangles are first compared by bounding box, so distant meshes are rejected cheaply. The result does not
+    /// depend on the scale of the meshes, since vertices only count as touching a plane within a tolerance relative to the triangles' size
+    ///
+    /// ```rust
+    /// use libpolymesh::prelude as pmf;
+    /// use libpolymesh::common::MeshDef;
+    ///
+    /// let triangle = |corners: [[f32; 3]; 3], scale: f32| MeshDef {
+    ///     color: pmf::PolyColor::green(),
+    ///     triangles: Some(vec![corners.map(|[x, y, z]| pmf::PolyVector { x: x * scale, y: y * scale, z: z * scale })]),
+    ///     double_sided: false,
+    ///     material: None,
+    ///     joints: None,
+    ///     weights: None,
+    ///     uvs: None
+    /// };
+    /// let floor = [[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]];
+    /// for scale in [1.0, 0.01, 100.0] {
+    ///     let intersects = |corners| triangle(floor, scale).intersects(&triangle(corners, scale));
+    ///
+    ///     // Passing through the floor
+    ///     assert!(intersects([[0.2, 0.2, -0.5], [0.3, 0.2, 0.5], [0.2, 0.3, 0.5]]));
+    ///
+    ///     // Crossing the floor's plane, but outside of the floor itself
+    ///     assert!(!intersects([[0.1, 0.1, 0.5], [0.9, 0.05, 0.5], [2.0, 2.0, -0.5]]));
+    ///
+    ///     // Floating above the floor
+    ///     assert!(!intersects([[0.0, 0.0, 0.5], [1.0, 0.0, 0.5], [0.0, 1.0, 0.5]]));
+    ///
+    ///     // Standing on the floor with one corner
+    ///     assert!(intersects([[0.2, 0.2, 0.0], [0.3, 0.2, 0.5], [0.2, 0.3, 0.5]]));
+    ///
+    ///     // Lying on the floor, overlapping it or beside it
+    ///     assert!(intersects([[0.2, 0.2, 0.0], [1.0, 0.2, 0.0], [0.2, 1.0, 0.0]]));
+    ///     assert!(!intersects([[1.0, 1.0, 0.0], [2.0, 1.0, 0.0], [1.0, 2.0, 0.0]]));
+    /// }
+    /// ```
+    pub fn intersects(&self, other: &MeshDef) -> bool {
+        let (triangles, other_triangles) = match (&self.triangles, &other.triangles) {
+            (Some(triangles), Some(other_triangles)) => (triangles, other_triangles),
+            _ => return false
+        };
+
+        // Skip the per-triangle work if the meshes are nowhere near each other
+        match (self.bounds(), other.bounds()) {
+            (Some(bounds), Some(other_bounds)) if boxes_overlap(bounds, other_bounds) => {},
+            _ => return false
+        }
+
+        // Check every pair of triangles with overlapping bounds
+        let other_bounds: Vec<_> = other_triangles.iter().map(|triangle| bounds_of_points(triangle.iter().copied()).unwrap()).collect();
+        triangles.iter().any(|triangle| {
+            let bounds = bounds_of_points(triangle.iter().copied()).unwrap();
+            other_triangles.iter().zip(other_bounds.iter()).any(|(other_triangle, other_bounds)| {
+                boxes_overlap(bounds, *other_bounds) && triangles_intersect(triangle, other_triangle)
+            })
+        })
+    }
+
+}
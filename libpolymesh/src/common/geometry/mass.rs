@@ -0,0 +1,83 @@
+use crate::common::{
+    MeshDef,
+    transform::PolyVector
+};
+
+/// Get a component of a vector by index
+fn component(vector: PolyVector, index: usize) -> f32 {
+    match index {
+        0 => vector.x,
+        1 => vector.y,
+        _ => vector.z
+    }
+}
+
+impl MeshDef {
+
+    /// Get the center of mass of this mesh, treating it as a solid of uniform density.
+    ///
+    /// This assumes a closed, outward-facing mesh (see `is_closed` and `orient_outward`). For open or flat meshes,
+    /// the average of all triangle corners is returned instead. A mesh without triangles has its centroid at the origin
+    pub fn centroid(&self) -> PolyVector {
+        let triangles = match &self.triangles {
+            Some(triangles) if !triangles.is_empty() => triangles,
+            _ => return PolyVector::zero()
+        };
+
+        // Weight the center of each tetrahedron formed with the origin by its signed volume
+        let volume = self.signed_volume();
+        if self.is_closed() && volume.abs() > f32::EPSILON {
+            let weighted = triangles.iter().fold(PolyVector::zero(), |sum, [a, b, c]| sum + (*a + *b + *c) * (a.dot(b.cross(*c)) / 24.0));
+            return weighted / volume;
+        }
+
+        // Fall back to the average corner
+        let sum = triangles.iter().fold(PolyVector::zero(), |sum, [a, b, c]| sum + *a + *b + *c);
+        sum / (triangles.len() * 3) as f32
+    }
+
+    /// Get the inertia tensor of this mesh about its `centroid`, treating it as a solid of uniform `density`.
+    ///
+    /// The mesh is split into tetrahedrons formed by each triangle and the origin, so this assumes a closed, outward-facing mesh.
+    /// Results for open meshes are not meaningful
+    pub fn inertia_tensor(&self, density: f32) -> [[f32; 3]; 3] {
+        let triangles = match &self.triangles {
+            Some(triangles) => triangles,
+            None => return [[0.0; 3]; 3]
+        };
+
+        // Sum the covariance of every tetrahedron about the origin
+        let mut covariance = [[0.0; 3]; 3];
+        for triangle in triangles {
+            let [a, b, c] = *triangle;
+            let determinant = a.dot(b.cross(c));
+            let sum = a + b + c;
+            for (i, row) in covariance.iter_mut().enumerate() {
+                for (j, value) in row.iter_mut().enumerate() {
+                    let corners: f32 = triangle.iter().map(|vertex| component(*vertex, i) * component(*vertex, j)).sum();
+                    *value += density * determinant / 120.0 * (component(sum, i) * component(sum, j) + corners);
+                }
+            }
+        }
+
+        // Move the covariance to be about the center of mass
+        let mass = density * self.signed_volume();
+        let centroid = self.centroid();
+        for (i, row) in covariance.iter_mut().enumerate() {
+            for (j, value) in row.iter_mut().enumerate() {
+                *value -= mass * component(centroid, i) * component(centroid, j);
+            }
+        }
+
+        // Convert the covariance into an inertia tensor
+        let trace = covariance[0][0] + covariance[1][1] + covariance[2][2];
+        let mut output = [[0.0; 3]; 3];
+        for (i, row) in output.iter_mut().enumerate() {
+            for (j, value) in row.iter_mut().enumerate() {
+                *value = if i == j { trace - covariance[i][j] } else { -covariance[i][j] };
+            }
+        }
+        output
+    }
+
+}
@@ -0,0 +1,46 @@
+use crate::common::{
+    MeshDef,
+    PolyMeshError
+};
+
+impl MeshDef {
+
+    /// Append the triangles of `other` to this mesh.
+    ///
+    /// A `MeshDef` only has one color, material, and `double_sided` flag, so meshes that differ in any of them are refused with
+    /// `PolyMeshError::IncompatibleMeshes` rather than silently losing one of the settings. The same goes for merging a
+    /// skinned mesh with an unskinned one, or a textured mesh with an untextured one. Skinning data and texture coordinates are appended along with the triangles
+    pub fn merge(&mut self, other: &MeshDef) -> Result<(), PolyMeshError> {
+        if self.color != other.color {
+            return Err(PolyMeshError::IncompatibleMeshes(format!("colors differ ({:?} and {:?})", self.color, other.color)));
+        }
+        if self.double_sided != other.double_sided {
+            return Err(PolyMeshError::IncompatibleMeshes("only one mesh is double sided".to_string()));
+        }
+        if self.material != other.material {
+            return Err(PolyMeshError::IncompatibleMeshes(format!("materials differ ({:?} and {:?})", self.material, other.material)));
+        }
+        if self.is_skinned() != other.is_skinned() {
+            return Err(PolyMeshError::IncompatibleMeshes("only one mesh is skinned".to_string()));
+        }
+        if self.uvs.is_some() != other.uvs.is_some() {
+            return Err(PolyMeshError::IncompatibleMeshes("only one mesh has texture coordinates".to_string()));
+        }
+
+        // Combine the triangle lists
+        if let Some(other_triangles) = &other.triangles {
+            self.triangles.get_or_insert_with(Vec::new).extend_from_slice(other_triangles);
+        }
+        if let (Some(joints), Some(other_joints)) = (self.joints.as_mut(), other.joints.as_ref()) {
+            joints.extend_from_slice(other_joints);
+        }
+        if let (Some(weights), Some(other_weights)) = (self.weights.as_mut(), other.weights.as_ref()) {
+            weights.extend_from_slice(other_weights);
+        }
+        if let (Some(uvs), Some(other_uvs)) = (self.uvs.as_mut(), other.uvs.as_ref()) {
+            uvs.extend_from_slice(other_uvs);
+        }
+        Ok(())
+    }
+
+}
@@ -0,0 +1,36 @@
+//! Geometry processing operations on `MeshDef`
+
+mod slice;
+mod volume;
+mod orient;
+mod mass;
+mod edges;
+mod merge;
+mod normals;
+mod offset;
+mod quality;
+mod intersect;
+mod weld;
+mod subdivide;
+mod sample;
+mod rotate;
+mod uv;
+mod interleave;
+mod strip;
+mod recenter;
+mod obb;
+
+pub use quality::MeshQuality;
+pub use subdivide::SubdivScheme;
+pub use interleave::VertexAttr;
+pub use strip::strip_triangles;
+pub use obb::Obb;
+
+use super::transform::PolyVector;
+
+/// Get a hashable key for a vertex position, used to match up corners with the same position between triangles
+pub(crate) fn vertex_key(point: PolyVector) -> [u32; 3] {
+
+    // Adding zero turns -0.0 into 0.0 so both hash the same
+    [(point.x + 0.0).to_bits(), (point.y + 0.0).to_bits(), (point.z + 0.0).to_bits()]
+}
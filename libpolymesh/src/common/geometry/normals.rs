@@ -0,0 +1,76 @@
+use crate::common::{
+    MeshDef,
+    transform::PolyVector
+};
+use super::vertex_key;
+use std::collections::HashMap;
+
+impl MeshDef {
+
+    /// Get a smooth normal for every triangle corner, matching the layout of `triangles`.
+    ///
+    /// Normals are computed by averaging the face normals (weighted by area) of every
+    /// triangle that touches a vertex position. Corners that only touch degenerate triangles get a zero normal
+    pub fn smooth_normals(&self) -> Vec<[PolyVector; 3]> {
+        let triangles = match &self.triangles {
+            Some(triangles) => triangles,
+            None => return Vec::new()
+        };
+
+        // Sum the area-weighted face normals around each vertex position
+        let mut sums: HashMap<[u32; 3], PolyVector> = HashMap::new();
+        for [a, b, c] in triangles {
+            let face_normal = (*b - *a).cross(*c - *a);
+            for point in &[*a, *b, *c] {
+                let sum = sums.entry(vertex_key(*point)).or_insert_with(PolyVector::zero);
+                *sum = *sum + face_normal;
+            }
+        }
+
+        // Normalize the sums
+        let normal_at = |point: PolyVector| {
+            let sum = sums[&vertex_key(point)];
+            let length = sum.dot(sum).sqrt();
+            if length > 0.0 { sum / length } else { PolyVector::zero() }
+        };
+        triangles.iter().map(|[a, b, c]| [normal_at(*a), normal_at(*b), normal_at(*c)]).collect()
+    }
+
+    /// Get a normal for every triangle corner like `smooth_normals`, but keep edges sharper than `crease_angle_degrees` hard.
+    ///
+    /// Each corner only averages the triangles around its position whose faces are within the crease angle of its own, so
+    /// 0 gives flat shading and 180 matches `smooth_normals`. Triangles never share corners, so no vertices need to be split
+    pub fn compute_normals_with_crease(&self, crease_angle_degrees: f32) -> Vec<[PolyVector; 3]> {
+        let triangles = match &self.triangles {
+            Some(triangles) => triangles,
+            None => return Vec::new()
+        };
+        let min_cos = crease_angle_degrees.to_radians().cos();
+        let normalize = |vector: PolyVector| {
+            let length = vector.dot(vector).sqrt();
+            if length > 0.0 { vector / length } else { PolyVector::zero() }
+        };
+
+        // Collect the area-weighted face normal of every triangle around each vertex position
+        let face_normals: Vec<PolyVector> = triangles.iter().map(|[a, b, c]| (*b - *a).cross(*c - *a)).collect();
+        let mut around: HashMap<[u32; 3], Vec<usize>> = HashMap::new();
+        for (i, triangle) in triangles.iter().enumerate() {
+            for point in triangle {
+                around.entry(vertex_key(*point)).or_default().push(i);
+            }
+        }
+
+        // Average the faces that are close enough to each corner's own face. A degenerate face has no direction, so it takes all of them
+        triangles.iter().enumerate().map(|(i, triangle)| {
+            let own = normalize(face_normals[i]);
+            let is_degenerate = own == PolyVector::zero();
+            triangle.map(|point| {
+                let sum = around[&vertex_key(point)].iter()
+                    .filter(|other| is_degenerate || **other == i || normalize(face_normals[**other]).dot(own) >= min_cos)
+                    .fold(PolyVector::zero(), |sum, other| sum + face_normals[*other]);
+                normalize(sum)
+            })
+        }).collect()
+    }
+
+}
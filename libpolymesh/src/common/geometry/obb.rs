@@ -0,0 +1,150 @@
+use crate::common::{
+    MeshDef,
+    transform::PolyVector
+};
+
+/// A box that can be rotated to fit a mesh, produced by `MeshDef::oriented_bounding_box`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Obb {
+
+    /// Center of the box
+    pub center: PolyVector,
+
+    /// Directions of the box's edges. These are unit length, perpendicular to each other, and right-handed
+    pub axes: [PolyVector; 3],
+
+    /// Distance from the center to each face of the box, measured along the matching axis
+    pub half_extents: PolyVector
+
+}
+
+impl Obb {
+
+    /// Get the eight corners of the box
+    pub fn corners(&self) -> [PolyVector; 8] {
+        let mut corners = [self.center; 8];
+        for (i, corner) in corners.iter_mut().enumerate() {
+            let signs = [(i & 1) as f32 * 2.0 - 1.0, ((i >> 1) & 1) as f32 * 2.0 - 1.0, ((i >> 2) & 1) as f32 * 2.0 - 1.0];
+            *corner = *corner
+                + self.axes[0] * (self.half_extents.x * signs[0])
+                + self.axes[1] * (self.half_extents.y * signs[1])
+                + self.axes[2] * (self.half_extents.z * signs[2]);
+        }
+        corners
+    }
+
+    /// Get the total area of the box's faces
+    fn surface_area(&self) -> f32 {
+        let half = self.half_extents;
+        8.0 * (half.x * half.y + half.y * half.z + half.z * half.x)
+    }
+
+}
+
+impl MeshDef {
+
+    /// Get a box that tightly fits this mesh, rotated to follow the directions its vertices are spread along.
+    ///
+    /// The axes come from a principal component analysis of the unique vertex positions (see `unique_vertices`), ordered from the
+    /// widest spread to the narrowest. Evenly spread points (ex: the corners of a cube) have no preferred direction, so the
+    /// axis-aligned box is used instead whenever it has less surface area. A mesh with no vertices gets an empty box at the origin
+    pub fn oriented_bounding_box(&self) -> Obb {
+        let vertices = self.unique_vertices();
+        let aabb = match self.bounds() {
+            Some((min, max)) => Obb {
+                center: (min + max) * 0.5,
+                axes: [PolyVector::unit_x(), PolyVector::unit_y(), PolyVector::unit_z()],
+                half_extents: (max - min) * 0.5
+            },
+            None => Obb {
+                center: PolyVector::zero(),
+                axes: [PolyVector::unit_x(), PolyVector::unit_y(), PolyVector::unit_z()],
+                half_extents: PolyVector::zero()
+            }
+        };
+        if vertices.len() < 2 {
+            return aabb;
+        }
+
+        // Build the covariance of the vertices in double precision
+        let count = vertices.len() as f64;
+        let mean = vertices.iter().fold([0.0f64; 3], |sum, point| [sum[0] + point.x as f64, sum[1] + point.y as f64, sum[2] + point.z as f64]);
+        let mean = [mean[0] / count, mean[1] / count, mean[2] / count];
+        let mut covariance = [[0.0f64; 3]; 3];
+        for point in &vertices {
+            let offset = [point.x as f64 - mean[0], point.y as f64 - mean[1], point.z as f64 - mean[2]];
+            for (row, a) in covariance.iter_mut().zip(offset.iter()) {
+                for (cell, b) in row.iter_mut().zip(offset.iter()) {
+                    *cell += a * b / count;
+                }
+            }
+        }
+
+        // Order the axes from the widest spread to the narrowest, then make sure they are right-handed
+        let (values, vectors) = symmetric_eigen(covariance);
+        let mut order = [0, 1, 2];
+        order.sort_by(|a, b| values[*b].total_cmp(&values[*a]));
+        let axis = |i: usize| {
+            let column = PolyVector { x: vectors[0][i] as f32, y: vectors[1][i] as f32, z: vectors[2][i] as f32 };
+            column / column.dot(column).sqrt()
+        };
+        let (first, second) = (axis(order[0]), axis(order[1]));
+        let axes = [first, second, first.cross(second)];
+
+        // Measure the vertices along each axis
+        let mut min = [f32::INFINITY; 3];
+        let mut max = [f32::NEG_INFINITY; 3];
+        for point in &vertices {
+            for i in 0..3 {
+                let distance = point.dot(axes[i]);
+                min[i] = min[i].min(distance);
+                max[i] = max[i].max(distance);
+            }
+        }
+        let center = (0..3).fold(PolyVector::zero(), |center, i| center + axes[i] * ((min[i] + max[i]) * 0.5));
+        let oriented = Obb {
+            center,
+            axes,
+            half_extents: PolyVector { x: (max[0] - min[0]) * 0.5, y: (max[1] - min[1]) * 0.5, z: (max[2] - min[2]) * 0.5 }
+        };
+
+        if aabb.surface_area() < oriented.surface_area() { aabb } else { oriented }
+    }
+
+}
+
+/// Get the eigenvalues and eigenvectors (as matrix columns) of a symmetric 3x3 matrix, using Jacobi rotations
+fn symmetric_eigen(matrix: [[f64; 3]; 3]) -> ([f64; 3], [[f64; 3]; 3]) {
+    let mut a = matrix;
+    let mut vectors = [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]];
+    for _ in 0..32 {
+
+        // Stop once everything off the diagonal is negligible
+        let off_diagonal = a[0][1] * a[0][1] + a[0][2] * a[0][2] + a[1][2] * a[1][2];
+        let diagonal = a[0][0] * a[0][0] + a[1][1] * a[1][1] + a[2][2] * a[2][2];
+        if off_diagonal <= diagonal * 1e-24 {
+            break;
+        }
+
+        // Zero out each off-diagonal element in turn
+        for (p, q) in [(0, 1), (0, 2), (1, 2)] {
+            if a[p][q] == 0.0 {
+                continue;
+            }
+            let theta = (a[q][q] - a[p][p]) / (2.0 * a[p][q]);
+            let t = theta.signum() / (theta.abs() + (theta * theta + 1.0).sqrt());
+            let c = 1.0 / (t * t + 1.0).sqrt();
+            let s = t * c;
+            for row in a.iter_mut().chain(vectors.iter_mut()) {
+                let (kp, kq) = (row[p], row[q]);
+                row[p] = c * kp - s * kq;
+                row[q] = s * kp + c * kq;
+            }
+            let (row_p, row_q) = (a[p], a[q]);
+            a[p] = [0, 1, 2].map(|k| c * row_p[k] - s * row_q[k]);
+            a[q] = [0, 1, 2].map(|k| s * row_p[k] + c * row_q[k]);
+        }
+
+    }
+    ([a[0][0], a[1][1], a[2][2]], vectors)
+}
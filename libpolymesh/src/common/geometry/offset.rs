@@ -0,0 +1,21 @@
+use crate::common::MeshDef;
+
+impl MeshDef {
+
+    /// Get a copy of this mesh with every vertex moved `distance` along its `smooth_normals` entry. A negative distance insets the surface.
+    ///
+    /// This is best-effort: large offsets on concave areas can make the surface intersect itself
+    pub fn offset_along_normals(&self, distance: f32) -> MeshDef {
+        let mut output = self.clone();
+        let normals = self.smooth_normals();
+        if let Some(triangles) = output.triangles.as_mut() {
+            for (triangle, triangle_normals) in triangles.iter_mut().zip(normals.iter()) {
+                for (point, normal) in triangle.iter_mut().zip(triangle_normals.iter()) {
+                    *point = *point + *normal * distance;
+                }
+            }
+        }
+        output
+    }
+
+}
@@ -0,0 +1,26 @@
+use crate::common::MeshDef;
+
+impl MeshDef {
+
+    /// Make a closed mesh face outwards by reversing the winding of every triangle if its `signed_volume` is negative.
+    ///
+    /// This assumes the triangles are already wound consistently with each other. Open meshes are left untouched.
+    /// Returns `true` if the mesh was changed
+    pub fn orient_outward(&mut self) -> bool {
+        if !self.is_closed() || self.signed_volume() >= 0.0 {
+            return false;
+        }
+
+        // Flip every triangle
+        let mut count = 0;
+        if let Some(triangles) = &mut self.triangles {
+            count = triangles.len();
+            for triangle in triangles.iter_mut() {
+                triangle.swap(1, 2);
+            }
+        }
+        (0..count).for_each(|index| self.swap_corner_attributes(index));
+        true
+    }
+
+}
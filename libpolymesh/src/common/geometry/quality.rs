@@ -0,0 +1,60 @@
+use crate::common::MeshDef;
+use super::vertex_key;
+use std::collections::HashMap;
+
+/// Summary of common problems in a mesh, produced by `MeshDef::quality_report`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct MeshQuality {
+
+    /// Number of triangles with no area
+    pub degenerate_triangles: usize,
+
+    /// Number of edges shared by more than two triangles
+    pub non_manifold_edges: usize,
+
+    /// Number of triangle corners that sit on a position already used by another corner, and could be welded together
+    pub duplicate_vertices: usize,
+
+    /// Set if two triangles sharing an edge run along it in the same direction, meaning one of them is flipped
+    pub inconsistent_winding: bool,
+
+    /// Set if every edge is shared by exactly two triangles (see `MeshDef::is_closed`)
+    pub closed: bool
+
+}
+
+impl MeshDef {
+
+    /// Check this mesh for common problems in one pass. Vertices are matched by exact position
+    pub fn quality_report(&self) -> MeshQuality {
+        let mut report = MeshQuality {
+            closed: self.is_closed(),
+            ..MeshQuality::default()
+        };
+
+        let mut positions = HashMap::new();
+        let mut directed_edges = HashMap::new();
+        let mut edges = HashMap::new();
+        for triangle in self.triangles.iter().flatten() {
+            let [a, b, c] = *triangle;
+            let normal = (b - a).cross(c - a);
+            if normal.dot(normal) == 0.0 {
+                report.degenerate_triangles += 1;
+            }
+
+            for i in 0..3 {
+                let start = vertex_key(triangle[i]);
+                let end = vertex_key(triangle[(i + 1) % 3]);
+                *positions.entry(start).or_insert(0) += 1;
+                *directed_edges.entry((start, end)).or_insert(0) += 1;
+                *edges.entry(if start < end { (start, end) } else { (end, start) }).or_insert(0) += 1;
+            }
+        }
+
+        report.duplicate_vertices = positions.values().map(|count| count - 1).sum();
+        report.non_manifold_edges = edges.values().filter(|count| **count > 2).count();
+        report.inconsistent_winding = directed_edges.values().any(|count| *count > 1);
+        report
+    }
+
+}
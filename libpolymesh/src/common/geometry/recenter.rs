@@ -0,0 +1,29 @@
+use crate::common::{
+    MeshDef,
+    transform::PolyVector
+};
+
+impl MeshDef {
+
+    /// Move this mesh so the centroid of its vertices sits at the origin, returning the centroid it was moved from.
+    ///
+    /// Geometry far from the origin (ex: georeferenced coordinates) loses precision, so this keeps the vertices small and leaves
+    /// the offset to be applied by a transform instead (see `PolyMesh::recenter_geometry`). A mesh with no vertices is left as-is
+    pub fn recenter(&mut self) -> PolyVector {
+        let triangles = match self.triangles.as_mut() {
+            Some(triangles) if !triangles.is_empty() => triangles,
+            _ => return PolyVector::zero()
+        };
+
+        // Sum in double precision, since the coordinates being fixed are the ones that lose precision in an f32 sum
+        let count = (triangles.len() * 3) as f64;
+        let sum = triangles.iter().flatten().fold([0.0f64; 3], |sum, point| [sum[0] + point.x as f64, sum[1] + point.y as f64, sum[2] + point.z as f64]);
+        let centroid = PolyVector { x: (sum[0] / count) as f32, y: (sum[1] / count) as f32, z: (sum[2] / count) as f32 };
+
+        for point in triangles.iter_mut().flatten() {
+            *point = *point - centroid;
+        }
+        centroid
+    }
+
+}
@@ -0,0 +1,37 @@
+use crate::common::{
+    MeshDef,
+    PolyMeshError,
+    transform::{
+        PolyVector,
+        PolyQuaternion
+    }
+};
+
+impl MeshDef {
+
+    /// Rotate every vertex of this mesh by `angle` radians about `axis`, through the origin.
+    ///
+    /// The axis does not need to be normalized, but fails with `PolyMeshError::InvalidDimensions` if it is zero-length or not finite.
+    /// Face normals are implied by triangle winding, so they follow the rotation without any extra work
+    pub fn rotate_axis_angle(&mut self, axis: PolyVector, angle: f32) -> Result<(), PolyMeshError> {
+        self.rotate_around(PolyVector::zero(), axis, angle)
+    }
+
+    /// Rotate every vertex of this mesh by `angle` radians about the line through `point` along `axis`. See `rotate_axis_angle`
+    pub fn rotate_around(&mut self, point: PolyVector, axis: PolyVector, angle: f32) -> Result<(), PolyMeshError> {
+        let length = axis.dot(axis).sqrt();
+        if !axis.is_finite() || length == 0.0 {
+            return Err(PolyMeshError::InvalidDimensions(format!("Rotation axis must be non-zero, got {:?}", axis)));
+        }
+
+        // Rotate each vertex relative to the pivot
+        let rotation = PolyQuaternion::from_axis_angle(axis / length, angle);
+        if let Some(triangles) = self.triangles.as_mut() {
+            for vertex in triangles.iter_mut().flatten() {
+                *vertex = rotation.rotate_vector(*vertex - point) + point;
+            }
+        }
+        Ok(())
+    }
+
+}
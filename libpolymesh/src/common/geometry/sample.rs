@@ -0,0 +1,54 @@
+use crate::common::{
+    MeshDef,
+    transform::PolyVector
+};
+use rand::Rng;
+
+impl MeshDef {
+
+    /// Get the area of every triangle, matching the layout of `triangles`
+    pub fn face_areas(&self) -> Vec<f32> {
+        self.triangles.iter().flatten().map(|[a, b, c]| {
+            let doubled = (*b - *a).cross(*c - *a);
+            doubled.dot(doubled).sqrt() * 0.5
+        }).collect()
+    }
+
+    /// Pick `count` points spread uniformly over the surface of this mesh.
+    ///
+    /// Each point lands on a triangle chosen with probability proportional to its area, at a uniformly random spot
+    /// within it. The same sequence from `rng` always gives the same points. A mesh with no area gives no points
+    pub fn sample_surface(&self, count: usize, rng: &mut impl Rng) -> Vec<PolyVector> {
+        let triangles = match &self.triangles {
+            Some(triangles) => triangles,
+            None => return Vec::new()
+        };
+
+        // Build a running total of the areas to search through
+        let mut total = 0.0;
+        let cumulative: Vec<f32> = self.face_areas().iter().map(|area| {
+            total += area;
+            total
+        }).collect();
+        if total <= 0.0 {
+            return Vec::new();
+        }
+
+        (0..count).map(|_| {
+
+            // Find the triangle the random area falls in, skipping over any with no area
+            let target = rng.gen::<f32>() * total;
+            let index = cumulative.partition_point(|sum| *sum <= target).min(triangles.len() - 1);
+            let [a, b, c] = triangles[index];
+
+            // Fold the unit square onto the triangle so points are spread evenly
+            let (mut u, mut v) = (rng.gen::<f32>(), rng.gen::<f32>());
+            if u + v > 1.0 {
+                u = 1.0 - u;
+                v = 1.0 - v;
+            }
+            a + (b - a) * u + (c - a) * v
+        }).collect()
+    }
+
+}
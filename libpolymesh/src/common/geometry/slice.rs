@@ -0,0 +1,181 @@
+use crate::common::{
+    MeshDef,
+    transform::PolyVector
+};
+
+/// Distance below which two cut points are considered the same point when building caps
+const CAP_WELD_DISTANCE: f32 = 1e-5;
+
+/// Distance from the plane (for a unit normal) below which a vertex is considered to lie on it
+const PLANE_EPSILON: f32 = 1e-6;
+
+impl MeshDef {
+
+    /// Cut this mesh with the plane of points `p` where `dot(plane_normal, p) == plane_d`.
+    ///
+    /// Returns `(front, back)`, where `front` holds the geometry on the side `plane_normal` points towards.
+    /// Triangles that straddle the plane are split along the cut, and vertices within a small distance of the plane are treated
+    /// as lying on it, so no zero-area slivers are produced. If `cap` is set, the cut is closed off on both halves.
+    /// Capping assumes the mesh is closed, and fans each cut outline from its center, so it is only exact for convex outlines.
+    /// Skinning data and texture coordinates are not carried over to either half
+    ///
+    /// ```rust
+    /// use libpolymesh::prelude as pmf;
+    /// use libpolymesh::common::MeshDef;
+    ///
+    /// let triangle = |corners: [[f32; 3]; 3]| MeshDef {
+    ///     color: pmf::PolyColor::green(),
+    ///     triangles: Some(vec![corners.map(|[x, y, z]| pmf::PolyVector { x, y, z })]),
+    ///     double_sided: false,
+    ///     material: None,
+    ///     joints: None,
+    ///     weights: None,
+    ///     uvs: None
+    /// };
+    /// let count = |mesh: &MeshDef| mesh.triangles.as_ref().map_or(0, Vec::len);
+    ///
+    /// // A plane passing a hair away from two corners leaves the triangle whole, rather than cutting off a sliver
+    /// let (front, back) = triangle([[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]]).slice(pmf::PolyVector::unit_x(), 1e-9, false);
+    /// assert_eq!((count(&front), count(&back)), (1, 0));
+    ///
+    /// // A plane through a corner splits the triangle into exactly one piece per side
+    /// let (front, back) = triangle([[0.0, 1.0, 0.0], [-1.0, -1.0, 0.0], [1.0, -1.0, 0.0]]).slice(pmf::PolyVector::unit_x(), 0.0, false);
+    /// assert_eq!((count(&front), count(&back)), (1, 1));
+    /// ```
+    pub fn slice(&self, plane_normal: PolyVector, plane_d: f32, cap: bool) -> (MeshDef, MeshDef) {
+        let mut front = Vec::new();
+        let mut back = Vec::new();
+        let mut cut_segments = Vec::new();
+
+        // Vertices closer to the plane than this are treated as lying on it
+        let tolerance = PLANE_EPSILON * plane_normal.dot(plane_normal).sqrt();
+
+        for triangle in self.triangles.iter().flatten() {
+            let distances = [0, 1, 2].map(|i| {
+                let distance = plane_normal.dot(triangle[i]) - plane_d;
+                if distance.abs() <= tolerance { 0.0 } else { distance }
+            });
+
+            // Triangles entirely on one side (or touching the plane) need no splitting
+            if distances.iter().all(|d| *d >= 0.0) {
+                front.push(*triangle);
+                continue;
+            }
+            if distances.iter().all(|d| *d <= 0.0) {
+
+                // An edge lying in the plane is still part of the cut outline
+                let on_plane: Vec<PolyVector> = (0..3).filter(|i| distances[*i] == 0.0).map(|i| triangle[i]).collect();
+                if on_plane.len() == 2 {
+                    cut_segments.push((on_plane[0], on_plane[1]));
+                }
+                back.push(*triangle);
+                continue;
+            }
+
+            // Walk the edges, sorting points onto each side and adding the crossing points to both.
+            // Points on the plane belong to both sides
+            let mut front_polygon = Vec::new();
+            let mut back_polygon = Vec::new();
+            let mut crossings = Vec::new();
+            for i in 0..3 {
+                let (a, b) = (triangle[i], triangle[(i + 1) % 3]);
+                let (da, db) = (distances[i], distances[(i + 1) % 3]);
+
+                if da >= 0.0 {
+                    front_polygon.push(a);
+                }
+                if da <= 0.0 {
+                    back_polygon.push(a);
+                }
+                if da == 0.0 {
+                    crossings.push(a);
+                }
+
+                if (da > 0.0 && db < 0.0) || (da < 0.0 && db > 0.0) {
+                    let crossing = PolyVector::lerp(a, b, da / (da - db));
+                    front_polygon.push(crossing);
+                    back_polygon.push(crossing);
+                    crossings.push(crossing);
+                }
+            }
+
+            // Fan the (convex) pieces back into triangles, dropping any sliver that collapsed to nothing
+            for (polygon, output) in [(&front_polygon, &mut front), (&back_polygon, &mut back)] {
+                for i in 1..polygon.len().saturating_sub(1) {
+                    let piece = [polygon[0], polygon[i], polygon[i + 1]];
+                    if !is_collapsed(&piece) {
+                        output.push(piece);
+                    }
+                }
+            }
+            if crossings.len() == 2 {
+                cut_segments.push((crossings[0], crossings[1]));
+            }
+        }
+
+        // Close off the cut
+        if cap {
+            for triangle in build_cap(cut_segments, plane_normal) {
+
+                // The cap of the front half faces backwards, and vice versa
+                front.push(triangle);
+                back.push([triangle[0], triangle[2], triangle[1]]);
+            }
+        }
+
+        (
+            MeshDef { color: self.color, triangles: Some(front), double_sided: self.double_sided, material: self.material.clone(), joints: None, weights: None, uvs: None },
+            MeshDef { color: self.color, triangles: Some(back), double_sided: self.double_sided, material: self.material.clone(), joints: None, weights: None, uvs: None }
+        )
+    }
+
+}
+
+/// Check if a triangle has no area compared to the size of its edges
+fn is_collapsed(triangle: &[PolyVector; 3]) -> bool {
+    let longest = (0..3).map(|i| {
+        let edge = triangle[(i + 1) % 3] - triangle[i];
+        edge.dot(edge)
+    }).fold(0.0, f32::max);
+    let normal = (triangle[1] - triangle[0]).cross(triangle[2] - triangle[0]);
+    normal.dot(normal).sqrt() <= PLANE_EPSILON * longest
+}
+
+/// Chain cut segments into outlines, and fan each outline into triangles facing away from `plane_normal`
+fn build_cap(mut segments: Vec<(PolyVector, PolyVector)>, plane_normal: PolyVector) -> Vec<[PolyVector; 3]> {
+    let mut triangles = Vec::new();
+    let close = |a: PolyVector, b: PolyVector| (a - b).dot(a - b) < CAP_WELD_DISTANCE * CAP_WELD_DISTANCE;
+
+    while let Some((start, mut end)) = segments.pop() {
+
+        // Follow connected segments until the outline closes or runs out
+        let mut outline = vec![start];
+        while !close(end, start) {
+            let next = segments.iter().position(|(a, b)| close(*a, end) || close(*b, end));
+            match next {
+                Some(i) => {
+                    let (a, b) = segments.swap_remove(i);
+                    outline.push(end);
+                    end = if close(a, end) { b } else { a };
+                },
+                None => break
+            }
+        }
+        if outline.len() < 3 {
+            continue;
+        }
+
+        // Fan the outline from its center
+        let center = outline.iter().fold(PolyVector::zero(), |sum, point| sum + *point) * (1.0 / outline.len() as f32);
+        for i in 0..outline.len() {
+            let (a, b) = (outline[i], outline[(i + 1) % outline.len()]);
+            if (a - center).cross(b - center).dot(plane_normal) > 0.0 {
+                triangles.push([center, b, a]);
+            } else {
+                triangles.push([center, a, b]);
+            }
+        }
+    }
+
+    triangles
+}
@@ -0,0 +1,117 @@
+use crate::common::MeshDef;
+use std::collections::{
+    HashMap,
+    HashSet
+};
+
+impl MeshDef {
+
+    /// Convert this mesh into triangle strips, as indices into `unique_vertices`.
+    ///
+    /// Strips are grown greedily: each one starts from the first unused triangle and keeps stepping onto an unused neighbour
+    /// that shares its last edge with matching winding. With `join` set, every strip is stitched into a single run with
+    /// degenerate triangles, keeping the winding of each strip intact. Degenerate triangles already in the mesh are dropped.
+    /// `strip_triangles` turns a strip back into a triangle list
+    ///
+    /// ```rust
+    /// use libpolymesh::prelude as pmf;
+    /// use libpolymesh::common::geometry::strip_triangles;
+    ///
+    /// let quad = pmf::make_quad(pmf::PolyVector::unit_y(), pmf::PolyVector::unit(), pmf::PolyVector::zero(), pmf::PolyVector::unit_x(), pmf::PolyColor::green());
+    /// let geometry = quad.geometry.unwrap();
+    /// let strips = geometry.to_triangle_strips(true);
+    /// assert_eq!(strips.len(), 1);
+    /// assert_eq!(strip_triangles(&strips[0]).len(), geometry.triangles.unwrap().len());
+    /// ```
+    pub fn to_triangle_strips(&self, join: bool) -> Vec<Vec<u32>> {
+        let triangles: Vec<[u32; 3]> = self.indexed_triangles().1.into_iter()
+            .filter(|[a, b, c]| a != b && b != c && c != a)
+            .collect();
+
+        // Map every directed edge to the triangles that contain it
+        let mut by_edge: HashMap<(u32, u32), Vec<usize>> = HashMap::new();
+        for (i, [a, b, c]) in triangles.iter().enumerate() {
+            for edge in &[(*a, *b), (*b, *c), (*c, *a)] {
+                by_edge.entry(*edge).or_default().push(i);
+            }
+        }
+
+        // Grow a strip from every triangle that is not in one yet, trying each way to start it
+        let mut used = vec![false; triangles.len()];
+        let mut strips = Vec::new();
+        for start in 0..triangles.len() {
+            if used[start] {
+                continue;
+            }
+            let (strip, members) = (0..3)
+                .map(|rotation| grow_strip(&triangles, &by_edge, &used, start, rotation))
+                .max_by_key(|(strip, _)| strip.len())
+                .unwrap();
+            members.into_iter().for_each(|member| used[member] = true);
+            strips.push(strip);
+        }
+
+        if join { join_strips(strips) } else { strips }
+    }
+
+}
+
+/// Get the triangles described by a triangle strip, skipping degenerate ones (ex: those used to join strips together).
+///
+/// Every other triangle in a strip is flipped, so each one comes out with the winding it had before stripping
+pub fn strip_triangles(strip: &[u32]) -> Vec<[u32; 3]> {
+    strip.windows(3).enumerate().filter_map(|(i, window)| {
+        let triangle = if i % 2 == 0 { [window[0], window[1], window[2]] } else { [window[1], window[0], window[2]] };
+        let [a, b, c] = triangle;
+        if a == b || b == c || c == a { None } else { Some(triangle) }
+    }).collect()
+}
+
+/// Grow a strip from the triangle at `start`, starting with its corners rotated by `rotation`.
+/// Returns the strip, along with every triangle in it
+fn grow_strip(triangles: &[[u32; 3]], by_edge: &HashMap<(u32, u32), Vec<usize>>, used: &[bool], start: usize, rotation: usize) -> (Vec<u32>, Vec<usize>) {
+    let [a, b, c] = triangles[start];
+    let mut strip = match rotation {
+        0 => vec![a, b, c],
+        1 => vec![b, c, a],
+        _ => vec![c, a, b]
+    };
+    let mut members = vec![start];
+    let mut taken: HashSet<usize> = members.iter().copied().collect();
+
+    loop {
+
+        // The next triangle reuses the last two corners, in an order that alternates along the strip
+        let (last, second_last) = (strip[strip.len() - 1], strip[strip.len() - 2]);
+        let edge = if strip.len() % 2 == 0 { (second_last, last) } else { (last, second_last) };
+        let next = by_edge.get(&edge).and_then(|candidates| candidates.iter().find(|i| !used[**i] && !taken.contains(*i)));
+        match next {
+            Some(&next) => {
+                let third = triangles[next].iter().copied().find(|corner| *corner != edge.0 && *corner != edge.1).unwrap();
+                strip.push(third);
+                members.push(next);
+                taken.insert(next);
+            },
+            None => break
+        }
+    }
+    (strip, members)
+}
+
+/// Stitch strips into one run by repeating vertices between them, which only adds degenerate triangles
+fn join_strips(strips: Vec<Vec<u32>>) -> Vec<Vec<u32>> {
+    let mut joined: Vec<u32> = Vec::new();
+    for strip in strips {
+        if let (Some(&last), Some(&first)) = (joined.last(), strip.first()) {
+            joined.push(last);
+            joined.push(first);
+
+            // Each strip must start on an even position to keep its winding
+            if joined.len() % 2 == 1 {
+                joined.push(first);
+            }
+        }
+        joined.extend(strip);
+    }
+    if joined.is_empty() { Vec::new() } else { vec![joined] }
+}
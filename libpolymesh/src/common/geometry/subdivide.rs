@@ -0,0 +1,188 @@
+use crate::common::{
+    MeshDef,
+    transform::PolyVector
+};
+use std::collections::{
+    HashMap,
+    HashSet
+};
+
+/// How `MeshDef::subdivide` places the vertices it creates
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum SubdivScheme {
+
+    /// Split every edge at its midpoint, keeping the shape exactly as it was
+    Midpoint,
+
+    /// Loop subdivision, which also moves the existing vertices to approach a smooth surface.
+    /// Open borders are smoothed as curves, so they stay attached to the rest of the mesh
+    Loop
+
+}
+
+impl MeshDef {
+
+    /// Split every triangle into four, `iterations` times over.
+    ///
+    /// Each edge gets exactly one new vertex, no matter how many triangles share it, so connected triangles stay connected.
+    /// Corners are matched by position (see `unique_vertices`). The color does not change. Texture coordinates and skinning on a new
+    /// vertex are the average of the two corners of the edge it splits, with the blended weights keeping the four strongest joints.
+    /// Per-corner data that does not have one entry for every corner is dropped
+    ///
+    /// ```rust
+    /// use libpolymesh::prelude as pmf;
+    /// use libpolymesh::common::{
+    ///     MeshDef,
+    ///     geometry::SubdivScheme
+    /// };
+    ///
+    /// let mut mesh = MeshDef {
+    ///     color: pmf::PolyColor::green(),
+    ///     triangles: Some(vec![[pmf::PolyVector::zero(), pmf::PolyVector::unit_x(), pmf::PolyVector::unit_y()]]),
+    ///     double_sided: false,
+    ///     material: None,
+    ///     joints: Some(vec![[0, 0, 0, 0], [1, 0, 0, 0], [1, 2, 0, 0]]),
+    ///     weights: Some(vec![[1.0, 0.0, 0.0, 0.0], [1.0, 0.0, 0.0, 0.0], [0.5, 0.5, 0.0, 0.0]]),
+    ///     uvs: Some(vec![[0.0, 0.0], [1.0, 0.0], [0.0, 1.0]])
+    /// };
+    /// mesh.subdivide(1, SubdivScheme::Midpoint);
+    ///
+    /// // The first new triangle runs from the first corner to the midpoints of its two edges
+    /// assert_eq!(mesh.uvs.as_ref().unwrap()[..3], [[0.0, 0.0], [0.5, 0.0], [0.0, 0.5]]);
+    /// assert_eq!(mesh.joints.as_ref().unwrap()[1], [0, 1, 0, 0]);
+    /// assert_eq!(mesh.weights.as_ref().unwrap()[1], [0.5, 0.5, 0.0, 0.0]);
+    ///
+    /// // The weights on every corner still add up to one
+    /// assert_eq!(mesh.weights.as_ref().unwrap().len(), 12);
+    /// assert!(mesh.weights.unwrap().iter().all(|weights| (weights.iter().sum::<f32>() - 1.0).abs() < 1e-6));
+    /// ```
+    pub fn subdivide(&mut self, iterations: usize, scheme: SubdivScheme) {
+        for _ in 0..iterations {
+            self.subdivide_once(scheme);
+        }
+    }
+
+    fn subdivide_once(&mut self, scheme: SubdivScheme) {
+        if self.triangles.is_none() {
+            return;
+        }
+        let (vertices, triangles) = self.indexed_triangles();
+
+        // Map every edge to the vertices opposite it
+        let edge_of = |a: u32, b: u32| if a < b { (a, b) } else { (b, a) };
+        let mut opposites: HashMap<(u32, u32), Vec<u32>> = HashMap::new();
+        for triangle in &triangles {
+            for i in 0..3 {
+                opposites.entry(edge_of(triangle[i], triangle[(i + 1) % 3])).or_default().push(triangle[(i + 2) % 3]);
+            }
+        }
+
+        // Place one new vertex on each edge
+        let edge_points: HashMap<(u32, u32), PolyVector> = opposites.iter().map(|(&(a, b), opposite)| {
+            let (start, end) = (vertices[a as usize], vertices[b as usize]);
+            let point = match (scheme, opposite.as_slice()) {
+                (SubdivScheme::Loop, [c, d]) => (start + end) * 0.375 + (vertices[*c as usize] + vertices[*d as usize]) * 0.125,
+                _ => (start + end) * 0.5
+            };
+            ((a, b), point)
+        }).collect();
+
+        // Move the existing vertices
+        let vertices = match scheme {
+            SubdivScheme::Midpoint => vertices,
+            SubdivScheme::Loop => smooth_vertices(&vertices, &opposites)
+        };
+
+        // Replace each triangle with four smaller ones
+        let mut output = Vec::with_capacity(triangles.len() * 4);
+        for &[a, b, c] in &triangles {
+            let (ab, bc, ca) = (edge_points[&edge_of(a, b)], edge_points[&edge_of(b, c)], edge_points[&edge_of(c, a)]);
+            let (a, b, c) = (vertices[a as usize], vertices[b as usize], vertices[c as usize]);
+            output.push([a, ab, ca]);
+            output.push([ab, b, bc]);
+            output.push([ca, bc, c]);
+            output.push([ab, bc, ca]);
+        }
+
+        // Split the per-corner data the same way, so seams between triangles are kept
+        let corner_count = triangles.len() * 3;
+        self.uvs = self.uvs.take().filter(|uvs| uvs.len() == corner_count).map(|uvs| {
+            split_corners(&uvs, |[u0, v0], [u1, v1]| [(u0 + u1) * 0.5, (v0 + v1) * 0.5])
+        });
+        let (joints, weights) = match (self.joints.take(), self.weights.take()) {
+            (Some(joints), Some(weights)) if joints.len() == corner_count && weights.len() == corner_count => {
+                let influences: Vec<([u16; 4], [f32; 4])> = joints.into_iter().zip(weights).collect();
+                let (joints, weights): (Vec<[u16; 4]>, Vec<[f32; 4]>) = split_corners(&influences, blend_influences).into_iter().unzip();
+                (Some(joints), Some(weights))
+            },
+            _ => (None, None)
+        };
+        self.joints = joints;
+        self.weights = weights;
+        self.triangles = Some(output);
+    }
+
+}
+
+/// Split the per-corner data of every triangle in the same layout as `subdivide_once`, using `midpoint` to make the new corners
+fn split_corners<T: Copy>(corners: &[T], midpoint: impl Fn(T, T) -> T) -> Vec<T> {
+    let mut output = Vec::with_capacity(corners.len() * 4);
+    for corner in corners.chunks_exact(3) {
+        let (a, b, c) = (corner[0], corner[1], corner[2]);
+        let (ab, bc, ca) = (midpoint(a, b), midpoint(b, c), midpoint(c, a));
+        output.extend_from_slice(&[a, ab, ca, ab, b, bc, ca, bc, c, ab, bc, ca]);
+    }
+    output
+}
+
+/// Blend the skinning of two corners equally, keeping the four strongest joints and renormalising their weights
+fn blend_influences(a: ([u16; 4], [f32; 4]), b: ([u16; 4], [f32; 4])) -> ([u16; 4], [f32; 4]) {
+
+    // Sum the weight given to each joint by either corner
+    let mut totals: Vec<(u16, f32)> = Vec::with_capacity(8);
+    for (joint, weight) in a.0.iter().zip(a.1.iter()).chain(b.0.iter().zip(b.1.iter())) {
+        match totals.iter_mut().find(|(existing, _)| existing == joint) {
+            Some((_, total)) => *total += weight * 0.5,
+            None => totals.push((*joint, weight * 0.5))
+        }
+    }
+
+    // Keep the strongest joints, padding with unweighted joint 0
+    totals.sort_by(|a, b| b.1.total_cmp(&a.1));
+    totals.resize(totals.len().max(4), (0, 0.0));
+    let sum: f32 = totals[..4].iter().map(|(_, weight)| weight).sum();
+    let scale = if sum > 0.0 { 1.0 / sum } else { 0.0 };
+    ([0, 1, 2, 3].map(|i| totals[i].0), [0, 1, 2, 3].map(|i| totals[i].1 * scale))
+}
+
+/// Get the Loop-smoothed position of every vertex, given the vertices opposite each edge.
+///
+/// Edges with one triangle (or more than two) are borders. A vertex on exactly two border edges is smoothed along the border,
+/// any other vertex touching a border is left in place, and interior vertices are averaged with all of their neighbours
+fn smooth_vertices(vertices: &[PolyVector], opposites: &HashMap<(u32, u32), Vec<u32>>) -> Vec<PolyVector> {
+    let mut neighbours: Vec<HashSet<u32>> = vec![HashSet::new(); vertices.len()];
+    let mut border_neighbours: Vec<Vec<u32>> = vec![Vec::new(); vertices.len()];
+    for (&(a, b), opposite) in opposites {
+        neighbours[a as usize].insert(b);
+        neighbours[b as usize].insert(a);
+        if opposite.len() != 2 {
+            border_neighbours[a as usize].push(b);
+            border_neighbours[b as usize].push(a);
+        }
+    }
+
+    vertices.iter().enumerate().map(|(i, point)| {
+        match border_neighbours[i].as_slice() {
+            [] => {
+
+                // Warren's weights for the interior
+                let count = neighbours[i].len() as f32;
+                let beta = if neighbours[i].len() == 3 { 3.0 / 16.0 } else { 3.0 / (8.0 * count) };
+                let sum = neighbours[i].iter().fold(PolyVector::zero(), |sum, neighbour| sum + vertices[*neighbour as usize]);
+                *point * (1.0 - count * beta) + sum * beta
+            },
+            [a, b] => *point * 0.75 + (vertices[*a as usize] + vertices[*b as usize]) * 0.125,
+            _ => *point
+        }
+    }).collect()
+}
@@ -0,0 +1,24 @@
+use crate::common::MeshDef;
+
+impl MeshDef {
+
+    /// Get the texture-space bounding box of this mesh as `(min_u, min_v, max_u, max_v)`, or `None` if it has no texture coordinates
+    pub fn uv_bounds(&self) -> Option<(f32, f32, f32, f32)> {
+        let uvs = self.uvs.as_ref().filter(|uvs| !uvs.is_empty())?;
+        Some(uvs.iter().fold(
+            (f32::INFINITY, f32::INFINITY, f32::NEG_INFINITY, f32::NEG_INFINITY),
+            |(min_u, min_v, max_u, max_v), [u, v]| (min_u.min(*u), min_v.min(*v), max_u.max(*u), max_v.max(*v))
+        ))
+    }
+
+    /// Scale every texture coordinate by `scale`, then move it by `offset`, to pack this mesh into part of a shared texture.
+    ///
+    /// Ex: a scale of `(0.5, 0.5)` and an offset of `(0.5, 0.0)` moves the unit square into its bottom-right quarter. Does nothing if there are no texture coordinates
+    pub fn offset_scale_uvs(&mut self, offset: (f32, f32), scale: (f32, f32)) {
+        for [u, v] in self.uvs.iter_mut().flatten() {
+            *u = *u * scale.0 + offset.0;
+            *v = *v * scale.1 + offset.1;
+        }
+    }
+
+}
@@ -0,0 +1,42 @@
+use crate::common::MeshDef;
+use super::vertex_key;
+use std::collections::HashMap;
+
+impl MeshDef {
+
+    /// Get the signed volume enclosed by this mesh. This is positive when triangles wind counter-clockwise when seen from outside.
+    ///
+    /// The result is only meaningful for closed meshes (see `is_closed`)
+    pub fn signed_volume(&self) -> f32 {
+        let triangles = match &self.triangles {
+            Some(triangles) => triangles,
+            None => return 0.0
+        };
+
+        // Sum the signed volumes of the tetrahedrons formed by each triangle and the origin
+        triangles.iter().map(|[a, b, c]| a.dot(b.cross(*c)) / 6.0).sum()
+    }
+
+    /// Check if this mesh is closed, meaning every edge is shared by exactly two triangles.
+    ///
+    /// Vertices are matched by exact position. A mesh without triangles is not closed
+    pub fn is_closed(&self) -> bool {
+        let triangles = match &self.triangles {
+            Some(triangles) if !triangles.is_empty() => triangles,
+            _ => return false
+        };
+
+        // Count how many triangles use each undirected edge
+        let mut edge_counts = HashMap::new();
+        for triangle in triangles {
+            for i in 0..3 {
+                let a = vertex_key(triangle[i]);
+                let b = vertex_key(triangle[(i + 1) % 3]);
+                *edge_counts.entry(if a < b { (a, b) } else { (b, a) }).or_insert(0) += 1;
+            }
+        }
+
+        edge_counts.values().all(|count| *count == 2)
+    }
+
+}
@@ -0,0 +1,69 @@
+use crate::common::{
+    MeshDef,
+    transform::PolyVector
+};
+use std::collections::HashMap;
+
+impl MeshDef {
+
+    /// Snap together triangle corners that are within `position_tolerance` of each other, but only if the faces they
+    /// belong to point within `normal_tolerance_degrees` of each other.
+    ///
+    /// `MeshDef` has no per-vertex normals, so each corner uses the normal of its own face. Corners on either side of a
+    /// hard edge keep their own positions, while near-duplicates on a smooth surface are moved onto the first corner
+    /// that matched them. Returns the number of corners that were moved
+    pub fn weld_with_attributes(&mut self, position_tolerance: f32, normal_tolerance_degrees: f32) -> usize {
+        let triangles = match &mut self.triangles {
+            Some(triangles) => triangles,
+            None => return 0
+        };
+        let max_angle = normal_tolerance_degrees.to_radians();
+
+        // Bucket corners that become welding targets into a grid, so only neighbouring cells need to be searched
+        let cell_size = if position_tolerance > 0.0 { position_tolerance } else { 1.0 };
+        let cell_of = |point: PolyVector| [
+            (point.x / cell_size).floor() as i64,
+            (point.y / cell_size).floor() as i64,
+            (point.z / cell_size).floor() as i64
+        ];
+        let mut targets: HashMap<[i64; 3], Vec<(PolyVector, PolyVector)>> = HashMap::new();
+
+        let mut moved = 0;
+        for triangle in triangles.iter_mut() {
+            let face_normal = (triangle[1] - triangle[0]).cross(triangle[2] - triangle[0]);
+            for corner in triangle.iter_mut() {
+                let cell = cell_of(*corner);
+
+                // Look for an earlier corner that is close enough, on a face that points the same way
+                let mut found = None;
+                'search: for dx in -1..=1 {
+                    for dy in -1..=1 {
+                        for dz in -1..=1 {
+                            let neighbour = [cell[0] + dx, cell[1] + dy, cell[2] + dz];
+                            for (position, normal) in targets.get(&neighbour).into_iter().flatten() {
+                                let offset = *position - *corner;
+                                if offset.dot(offset).sqrt() <= position_tolerance && normal.angle_between(face_normal) <= max_angle {
+                                    found = Some(*position);
+                                    break 'search;
+                                }
+                            }
+                        }
+                    }
+                }
+
+                // Either snap onto the match, or become a target for later corners
+                match found {
+                    Some(position) => {
+                        if position != *corner {
+                            *corner = position;
+                            moved += 1;
+                        }
+                    },
+                    None => targets.entry(cell).or_default().push((*corner, face_normal))
+                }
+            }
+        }
+        moved
+    }
+
+}
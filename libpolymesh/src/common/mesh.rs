@@ -1,22 +1,96 @@
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
 use super::{
-    transform::PolyVector,
+    transform::{
+        PolyVector,
+        PolyMatrix4,
+        PolyQuaternion,
+        PolyTransform,
+        Axis
+    },
     serialization::data::{
         polymeta::{
             PolyMeta,
             PolyChildReference,
+            PathMode,
             LATEST_POLY_META_VERSION
         },
-        mesh::MeshDef
+        mesh::{
+            MeshDef,
+            bounds_of_points
+        }
     }
 };
+use super::PolyMeshError;
+use crate::util::normalize_tree_path;
+use serde_json::{
+    Map,
+    Value
+};
 use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// Metadata keys that are managed by typed helpers on `PolyMesh`, and should not be written directly
+pub const RESERVED_METADATA_KEYS: &[&str] = &["name", "_beta_runtime_culling"];
+
+/// Read a boolean metadata value. `on`, `true`, `yes`, and `1` (in any case) are true, and everything else is false.
+///
+/// Every boolean metadata field goes through this, so tools that write different spellings still agree
+///
+/// ```rust
+/// use libpolymesh::common::mesh::parse_meta_bool;
+///
+/// for value in &["on", "true", "yes", "1", "ON", "True", "YES"] {
+///     assert!(parse_meta_bool(value));
+/// }
+/// for value in &["off", "false", "no", "0", "", "enabled"] {
+///     assert!(!parse_meta_bool(value));
+/// }
+/// ```
+pub fn parse_meta_bool(value: &str) -> bool {
+    ["on", "true", "yes", "1"].iter().any(|truthy| value.eq_ignore_ascii_case(truthy))
+}
 
+/// The kind of a mesh node.
+///
+/// Stored in `polymeta.json` as the variant name, so hand-written files can use it directly:
+///
+/// ```rust
+/// use libpolymesh::prelude as pmf;
+///
+/// assert_eq!(serde_json::to_string(&pmf::MeshType::Group).unwrap(), "\"Group\"");
+/// assert_eq!(serde_json::to_string(&pmf::MeshType::Geometry).unwrap(), "\"Geometry\"");
+/// assert_eq!(serde_json::to_string(&pmf::MeshType::GeoGroup).unwrap(), "\"GeoGroup\"");
+/// assert_eq!(serde_json::to_string(&pmf::MeshType::Locator).unwrap(), "\"Locator\"");
+/// assert_eq!(serde_json::from_str::<pmf::MeshType>("\"GeoGroup\"").unwrap(), pmf::MeshType::GeoGroup);
+/// assert!(serde_json::from_str::<pmf::MeshType>("1").is_err());
+/// ```
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Copy)]
+#[serde(rename_all = "PascalCase")]
 pub enum MeshType {
     Group,
     Geometry,
-    GeoGroup
+    GeoGroup,
+
+    /// A node with neither geometry nor children, used purely as a position (ex: attachment points)
+    Locator
+}
+
+/// Generate a random (version 4) UUID string
+fn new_uuid_v4() -> String {
+    let mut bytes: [u8; 16] = rand::random();
+
+    // Mark the UUID as version 4, variant 1
+    bytes[6] = (bytes[6] & 0x0f) | 0x40;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+
+    let hex: Vec<String> = bytes.iter().map(|byte| format!("{:02x}", byte)).collect();
+    format!("{}-{}-{}-{}-{}", hex[0..4].concat(), hex[4..6].concat(), hex[6..8].concat(), hex[8..10].concat(), hex[10..16].concat())
+}
+
+/// Hash a tree path into a 64-bit ID with FNV-1a, as 16 hex digits. Unlike the standard library's hasher, this never changes between builds
+fn path_id(path: &str) -> String {
+    let hash = path.bytes().fold(0xcbf29ce484222325u64, |hash, byte| (hash ^ byte as u64).wrapping_mul(0x100000001b3));
+    format!("{:016x}", hash)
 }
 
 /// TransPolyMeshPtr is a small wrapper around a reference to a PolyMesh, describing a transformation on the mesh
@@ -29,20 +103,35 @@ pub struct TransPolyMeshPtr {
     /// Mesh reference
     pub mesh: Box<PolyMesh>,
 
-    /// Optional translation
-    pub translation: Option<PolyVector>
+    /// Transform applied to the mesh
+    pub transform: PolyTransform
 
 }
 
 impl TransPolyMeshPtr {
 
-    pub fn get_translation(&self) -> PolyVector {
-        match self.translation {
-            Some(x) => x,
-            None => PolyVector::zero()
+    /// Create a pointer to `mesh` at `path`, optionally translated
+    pub fn new(path: String, mesh: PolyMesh, translation: Option<PolyVector>) -> Self {
+        Self {
+            path,
+            mesh: Box::new(mesh),
+            transform: PolyTransform {
+                translation,
+                ..PolyTransform::identity()
+            }
         }
     }
 
+    /// Set the translation of this pointer, keeping the rest of its transform
+    pub fn with_translation(mut self, translation: PolyVector) -> Self {
+        self.transform.translation = Some(translation);
+        self
+    }
+
+    pub fn get_translation(&self) -> PolyVector {
+        self.transform.get_translation()
+    }
+
     pub fn new_from_transform_optional(&self, other: Option<&TransPolyMeshPtr>) -> Self {
         match other {
             Some(val) => self.new_from_transform(val),
@@ -50,22 +139,47 @@ impl TransPolyMeshPtr {
         }
     }
 
-    pub fn new_from_transform(&self, other: &TransPolyMeshPtr) -> Self {
-        
-        // Get both translations
-        let this_translation = self.get_translation();
-        let other_translation = other.get_translation();
+    /// Check if this pointer's transform collapses the referenced mesh (ex: a zero scale). See `PolyTransform::is_degenerate`
+    pub fn is_degenerate(&self) -> bool {
+        self.transform.is_degenerate()
+    }
+
+    /// Get the bounding box of the referenced mesh in the parent's space, as `(min, max)`.
+    ///
+    /// All 8 corners of the mesh's local bounding box are transformed, so this stays conservative under rotation
+    pub fn transformed_bounds(&self) -> Option<(PolyVector, PolyVector)> {
+        let (min, max) = self.mesh.bounds()?;
+        let corners = (0..8).map(|i| PolyVector {
+            x: if i & 1 == 0 { min.x } else { max.x },
+            y: if i & 2 == 0 { min.y } else { max.y },
+            z: if i & 4 == 0 { min.z } else { max.z }
+        });
+        bounds_of_points(corners.map(|corner| self.transform.transform_point(corner)))
+    }
 
-        // Create a new TransPolyMeshPtr
+    /// Create a copy of this pointer, with its transform composed with a parent transform
+    pub fn new_from_transform(&self, other: &TransPolyMeshPtr) -> Self {
         Self {
             path: self.path.to_string(),
             mesh: self.mesh.clone(),
-            translation: Some(this_translation + other_translation)
+            transform: self.transform.compose(&other.transform)
         }
     }
 
 }
 
+/// Where to find a deferred mesh that has not been loaded yet (see `PolyMesh::deferred_source`)
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeferredSource {
+
+    /// Directory the mesh will be read from
+    pub path: PathBuf,
+
+    /// The mesh's precomputed bounding box from its `polymeta.json`, in its local space, if it has one
+    pub bounds: Option<(PolyVector, PolyVector)>
+
+}
+
 /// A PolyMesh is any mesh, weather it contains geometry, other meshes, or a mix of both
 #[derive(Debug, Clone, PartialEq)]
 pub struct PolyMesh {
@@ -73,14 +187,39 @@ pub struct PolyMesh {
     /// The type of this mesh
     pub mesh_type: MeshType,
 
+    /// Optional stable identifier for this mesh (see `ensure_uuid`)
+    pub uuid: Option<String>,
+
     /// Possible geometry for this mesh
     pub geometry: Option<MeshDef>,
 
     /// Arbitrary metadata
     pub metadata: HashMap<String, String>,
 
+    /// Arbitrary binary data
+    pub blobs: HashMap<String, Vec<u8>>,
+
+    /// Arbitrary structured data (ex: nested objects or arrays), kept separate from the string `metadata`
+    pub extras: Map<String, Value>,
+
     /// All children of this mesh
-    pub children: Vec<TransPolyMeshPtr>
+    pub children: Vec<TransPolyMeshPtr>,
+
+    /// Optional free-form notes that are kept across saves, but never affect the mesh itself
+    pub notes: Option<String>,
+
+    /// If this mesh (and everything under it) is included when flattening
+    pub visible: bool,
+
+    /// Optional size of one unit of this scene in meters (ex: 0.01 for centimeters). Only read from the root, where unset means 1.0
+    pub unit_scale: Option<f32>,
+
+    /// If the parent references this mesh as deferred, meaning its contents are only loaded on request (see `FlatPolyMesh::load_deferred`)
+    pub deferred: bool,
+
+    /// Set on the placeholder left in place of a deferred mesh that has not been loaded yet. The placeholder keeps the mesh's own
+    /// `polymeta.json` data, but has no geometry or children. Writing a placeholder writes it as an empty group, so load it first to keep its contents
+    pub deferred_source: Option<DeferredSource>
 
 }
 
@@ -90,37 +229,122 @@ impl PolyMesh {
     pub fn new(mesh_type: MeshType, geometry: Option<MeshDef>) -> Self {
         PolyMesh {
             mesh_type,
+            uuid: None,
             geometry,
             metadata: HashMap::new(),
-            children: Vec::new()
+            blobs: HashMap::new(),
+            extras: Map::new(),
+            children: Vec::new(),
+            notes: None,
+            visible: true,
+            unit_scale: None,
+            deferred: false,
+            deferred_source: None
         }
     }
 
-    /// Add arbitrary data to the mesh
+    /// Build a tree from already-flattened geometry: a `Group` root with one untransformed `Geometry` child per mesh.
+    ///
+    /// Children are placed at `/mesh_<index>_geo`. Each one is named from `names` if it has an entry at the same index,
+    /// and left unnamed otherwise
+    pub fn from_flat(geometry: Vec<MeshDef>, names: Option<Vec<String>>) -> Self {
+        let mut names = names.unwrap_or_default().into_iter();
+        let mut root = PolyMesh::new(MeshType::Group, None);
+        for (i, mesh_def) in geometry.into_iter().enumerate() {
+            let mut child = PolyMesh::new(MeshType::Geometry, Some(mesh_def));
+            if let Some(name) = names.next() {
+                child.set_name(name);
+            }
+            root.add_child(TransPolyMeshPtr::new(format!("/mesh_{}_geo", i), child, None));
+        }
+        root
+    }
+
+    /// Add arbitrary data to the mesh.
+    ///
+    /// Writing one of the `RESERVED_METADATA_KEYS` this way still works, but logs a warning, since those keys have typed helpers (ex: `set_name`)
     pub fn add_metadata(&mut self, key: String, value: String) {
+        if RESERVED_METADATA_KEYS.contains(&key.as_str()) {
+            log::warn!("Metadata key \"{}\" is reserved, and should be set through its typed helper", key);
+        }
         self.metadata.insert(key, value);
     }
 
+    /// Strict version of `add_metadata` that refuses to write any of the `RESERVED_METADATA_KEYS`
+    pub fn try_add_metadata(&mut self, key: String, value: String) -> Result<(), PolyMeshError> {
+        if RESERVED_METADATA_KEYS.contains(&key.as_str()) {
+            return Err(PolyMeshError::ReservedMetadataKey(key));
+        }
+        self.metadata.insert(key, value);
+        Ok(())
+    }
+
+    /// Add arbitrary binary data to the mesh
+    pub fn set_blob(&mut self, key: String, value: Vec<u8>) {
+        self.blobs.insert(key, value);
+    }
+
+    /// Try to get arbitrary binary data
+    pub fn get_blob(&self, key: &str) -> Option<&Vec<u8>> {
+        self.blobs.get(key)
+    }
+
+    /// Remove arbitrary binary data from the mesh, returning it if it existed
+    pub fn remove_blob(&mut self, key: &str) -> Option<Vec<u8>> {
+        self.blobs.remove(key)
+    }
+
+    /// Add arbitrary structured data to the mesh
+    pub fn set_extra(&mut self, key: String, value: Value) {
+        self.extras.insert(key, value);
+    }
+
+    /// Try to get arbitrary structured data
+    pub fn get_extra(&self, key: &str) -> Option<&Value> {
+        self.extras.get(key)
+    }
+
+    /// Remove arbitrary structured data from the mesh, returning it if it existed
+    pub fn remove_extra(&mut self, key: &str) -> Option<Value> {
+        self.extras.remove(key)
+    }
+
     /// Add a child to the mesh
     pub fn add_child(&mut self, child: TransPolyMeshPtr) {
         self.children.push(child);
     }
 
-    // Try to get arbitrary metadata
-    pub fn try_get_meta_field(&self, key: &str) -> Result<&String, ()> {
-        if self.metadata.contains_key(key) {
-            Ok(&self.metadata[key])
-        } else {
-            Err(())
+    /// Try to get arbitrary metadata
+    pub fn try_get_meta_field(&self, key: &str) -> Option<&String> {
+        self.metadata.get(key)
+    }
+
+    /// Parse the metadata field `key` as JSON into any deserializable type (ex: a struct stored as a JSON object string).
+    ///
+    /// Fails with `PolyMeshError::MissingMetadata` if the field is not set, or `PolyMeshError::Json` if it does not parse
+    pub fn get_meta_parsed<T: DeserializeOwned>(&self, key: &str) -> Result<T, PolyMeshError> {
+        match self.try_get_meta_field(key) {
+            Some(value) => Ok(serde_json::from_str(value)?),
+            None => Err(PolyMeshError::MissingMetadata(key.to_string()))
         }
     }
 
-    /// Try to fetch the mesh name from metadata
+    /// Read the metadata field `key` as a boolean (see `parse_meta_bool`), treating an unset field as false
+    pub fn get_meta_bool(&self, key: &str) -> bool {
+        self.try_get_meta_field(key).is_some_and(|value| parse_meta_bool(value))
+    }
+
+    /// Try to fetch the mesh name from metadata, falling back to `Unnamed`
     pub fn get_name(&self) -> String {
-        return match self.try_get_meta_field("name") {
-            Ok(name) => name.to_string(),
-            Err(_) => "Unnamed".to_string()
-        };
+        self.name_or(|_| "Unnamed".to_string())
+    }
+
+    /// Fetch the mesh name from metadata, or build one with `fallback` if it has none (ex: from a source path or a counter)
+    pub fn name_or(&self, fallback: impl Fn(&PolyMesh) -> String) -> String {
+        match self.try_get_meta_field("name") {
+            Some(name) => name.to_string(),
+            None => fallback(self)
+        }
     }
 
     /// Set the mesh name
@@ -130,14 +354,61 @@ impl PolyMesh {
 
     /// Get if this mesh is requesting the BETA "Runtime Culling" feature
     pub fn uses_runtime_culling(&self) -> bool {
-        return match self.try_get_meta_field("_beta_runtime_culling") {
-            Ok(result) => result == "on",
-            Err(_) => false
-        };
+        self.get_meta_bool("_beta_runtime_culling")
     }
 
     pub fn enable_runtime_culling(&mut self) {
-        self.add_metadata("_beta_runtime_culling".to_string(), "on".to_string());
+        self.metadata.insert("_beta_runtime_culling".to_string(), "on".to_string());
+    }
+
+    /// Explicitly turn off runtime culling for this mesh, even if a parent enables it
+    pub fn disable_runtime_culling(&mut self) {
+        self.metadata.insert("_beta_runtime_culling".to_string(), "off".to_string());
+    }
+
+    /// Get if runtime culling applies to this mesh, given the setting inherited from its parent.
+    ///
+    /// A mesh with the setting uses its own value (read through `parse_meta_bool`, so `off` or any other value is false), and an unset mesh uses `inherited`
+    pub fn resolve_runtime_culling(&self, inherited: bool) -> bool {
+        match self.try_get_meta_field("_beta_runtime_culling") {
+            Some(value) => parse_meta_bool(value),
+            None => inherited
+        }
+    }
+
+    /// Give this mesh a random (version 4) UUID if it does not already have one
+    pub fn ensure_uuid(&mut self) {
+        if self.uuid.is_none() {
+            self.uuid = Some(new_uuid_v4());
+        }
+    }
+
+    /// Set the `id` metadata of this mesh and every mesh under it to a hash of its tree path, with this mesh at `/`.
+    ///
+    /// These IDs are the same every time the same structure is loaded, but change whenever a mesh is moved or renamed, which
+    /// makes them suited to path-based references (use `ensure_all_uuids` for IDs that follow a mesh around instead).
+    /// The hash is 64 bits, so even a scene with a million meshes has about a one in 37 million chance of any two sharing an ID.
+    /// Meshes referenced from several places get a different ID at each place
+    pub fn assign_path_ids(&mut self) {
+        self.assign_path_ids_recursive("");
+    }
+
+    fn assign_path_ids_recursive(&mut self, path: &str) {
+        self.metadata.insert("id".to_string(), path_id(&normalize_tree_path(path)));
+        for child in self.children.iter_mut() {
+            let child_path = format!("{}{}", path, child.path);
+            child.mesh.assign_path_ids_recursive(&child_path);
+        }
+    }
+
+    /// Give this mesh and every mesh under it a UUID if it does not already have one.
+    ///
+    /// Meshes referenced from several places are separate copies in memory, so each reference gets its own UUID unless one was loaded from disk
+    pub fn ensure_all_uuids(&mut self) {
+        self.ensure_uuid();
+        for child in self.children.iter_mut() {
+            child.mesh.ensure_all_uuids();
+        }
     }
 
     /// Converts this mesh into a PolyMeta object that describes it
@@ -146,24 +417,383 @@ impl PolyMesh {
         // Collect children
         let mut children = Vec::new();
         for child in &self.children {
+            // Transforms that do nothing are written without any components
             children.push(PolyChildReference {
                 path: (*child.path).to_string(),
-                translation: child.translation
+                transform: if child.transform.is_identity(0.0) { PolyTransform::IDENTITY } else { child.transform },
+                path_mode: PathMode::RelativeToParent,
+                deferred: child.mesh.deferred
             })
         }
 
         // Build output
         return PolyMeta {
             version: LATEST_POLY_META_VERSION,
+            uuid: self.uuid.clone(),
             mesh_type: self.mesh_type,
             metadata: self.metadata.clone(),
-            children: children
+            blobs: self.blobs.clone(),
+            extras: self.extras.clone(),
+            children: children,
+            notes: self.notes.clone(),
+            visible: self.visible,
+            unit_scale: self.unit_scale,
+            bounds: None,
+            geometry: None
+        };
+    }
+
+    /// Find the chain of children leading from this mesh to the mesh at `path`.
+    ///
+    /// Paths are built by joining child paths from the root down (ex: `/model_0_gr/faces_gr/positive_x_geo`).
+    /// If multiple children share a path, the first one is used. The root itself is `/` (or an empty string).
+    pub fn resolve_path(&self, path: &str) -> Option<Vec<&TransPolyMeshPtr>> {
+        let indices = self.resolve_path_indices(path)?;
+
+        // Follow the indices down the tree
+        let mut chain = Vec::new();
+        let mut mesh = self;
+        for i in indices {
+            chain.push(&mesh.children[i]);
+            mesh = &mesh.children[i].mesh;
+        }
+        Some(chain)
+    }
+
+    /// Get a mutable reference to the mesh at `path`. See `resolve_path` for how paths are matched
+    pub fn get_mesh_mut(&mut self, path: &str) -> Option<&mut PolyMesh> {
+        let indices = self.resolve_path_indices(path)?;
+        let mut mesh = self;
+        for i in indices {
+            mesh = &mut mesh.children[i].mesh;
+        }
+        Some(mesh)
+    }
+
+    fn resolve_path_indices(&self, path: &str) -> Option<Vec<usize>> {
+
+        // Check if the end of the path has been reached
+        if path.is_empty() || path == "/" {
+            return Some(Vec::new());
+        }
+
+        // Search for a child that matches the start of the path
+        for (i, child) in self.children.iter().enumerate() {
+            if let Some(remaining) = path.strip_prefix(child.path.as_str()) {
+
+                // Only accept matches on whole path segments
+                if !remaining.is_empty() && !remaining.starts_with('/') {
+                    continue;
+                }
+
+                // Search the rest of the path through this child
+                if let Some(mut indices) = child.mesh.resolve_path_indices(remaining) {
+                    indices.insert(0, i);
+                    return Some(indices);
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Get the accumulated transform of the mesh at `path`, relative to this mesh
+    pub fn world_matrix(&self, path: &str) -> Option<PolyMatrix4> {
+        let chain = self.resolve_path(path)?;
+        Some(chain.iter().fold(PolyMatrix4::identity(), |matrix, child| {
+            matrix * child.transform.to_matrix()
+        }))
+    }
+
+    /// Get the accumulated transform of the mesh at `path`, relative to this mesh
+    pub fn world_transform(&self, path: &str) -> Option<PolyTransform> {
+        let chain = self.resolve_path(path)?;
+        Some(chain.iter().fold(PolyTransform::identity(), |transform, child| child.transform.compose(&transform)))
+    }
+
+    /// Get the accumulated translation, rotation, and scale of the mesh at `path`, relative to this mesh.
+    ///
+    /// The transforms along the path are multiplied out and then split apart again (see `PolyMatrix4::decompose`), so this
+    /// stays accurate where `world_transform` has to approximate. Returns `None` if the path does not resolve or the transform collapses space
+    pub fn world_trs(&self, path: &str) -> Option<(PolyVector, PolyQuaternion, PolyVector)> {
+        self.world_matrix(path)?.decompose()
+    }
+
+    /// Get a deep copy of the mesh at `path` as a new root. As a root, it sits at the origin with no transform
+    pub fn extract_subtree(&self, path: &str) -> Option<PolyMesh> {
+        let chain = self.resolve_path(path)?;
+        Some(match chain.last() {
+            Some(child) => child.mesh.as_ref().clone(),
+            None => self.clone()
+        })
+    }
+
+    /// Like `extract_subtree`, but keeps the mesh where it was by wrapping it in a new `Group` root that applies its accumulated transform
+    pub fn extract_subtree_preserving_transform(&self, path: &str) -> Option<PolyMesh> {
+        let chain = self.resolve_path(path)?;
+        let child = match chain.last() {
+            Some(child) => child,
+            None => return Some(self.clone())
+        };
+
+        // Build a new root around the child
+        let mut root = PolyMesh::new(MeshType::Group, None);
+        root.set_name(child.mesh.get_name());
+        root.add_child(TransPolyMeshPtr {
+            path: child.path.to_string(),
+            mesh: child.mesh.clone(),
+            transform: self.world_transform(path)?
+        });
+        Some(root)
+    }
+
+    /// Mirror the mesh at `path` and everything under it across the plane perpendicular to `axis`, through that mesh's origin.
+    ///
+    /// All geometry has its winding reversed so it is not inside-out, and the transforms of all children are mirrored too.
+    pub fn mirror_subtree(&mut self, path: &str, axis: Axis) -> Result<(), PolyMeshError> {
+        match self.get_mesh_mut(path) {
+            Some(mesh) => {
+                mesh.mirror_recursive(axis);
+                Ok(())
+            },
+            None => Err(PolyMeshError::UnresolvedPath(path.to_string()))
+        }
+    }
+
+    /// Recenter the geometry of every mesh under this one (see `MeshDef::recenter`), moving each one's transform to compensate.
+    ///
+    /// The world-space position of every vertex stays the same (up to rounding): each mesh's translation takes on the offset,
+    /// and its children are moved back by it. This mesh's own geometry is left alone, since it has no transform to absorb the offset
+    pub fn recenter_geometry(&mut self) {
+        for child in self.children.iter_mut() {
+            let offset = child.mesh.geometry.as_mut().map_or(PolyVector::zero(), MeshDef::recenter);
+            if offset != PolyVector::zero() {
+
+                // Moving the geometry by the offset is the same as applying it before the rest of the transform
+                let transform = &mut child.transform;
+                transform.translation = Some(transform.get_translation() + transform.get_rotation().rotate_vector(offset.component_mul(transform.get_scale())));
+                for grandchild in child.mesh.children.iter_mut() {
+                    grandchild.transform.translation = Some(grandchild.transform.get_translation() - offset);
+                }
+            }
+            child.mesh.recenter_geometry();
+        }
+    }
+
+    /// Reset the transform of the mesh at `path` to identity (a "freeze transform"), without moving anything on screen.
+    ///
+    /// The removed transform is baked into the mesh's own geometry, and composed into the transform of each of its direct children.
+    /// Fails with `PolyMeshError::UnresolvedPath` if there is no mesh at `path`, or `PolyMeshError::UnrepresentableTransform` (leaving
+    /// everything as it was) if a child is rotated under a non-uniform scale, since that skews the child in a way no `PolyTransform` can.
+    /// The root has no transform, so resetting it does nothing
+    ///
+    /// ```rust
+    /// use libpolymesh::prelude as pmf;
+    /// use libpolymesh::common::{PolyMesh, MeshType, TransPolyMeshPtr, transform::PolyQuaternion};
+    ///
+    /// let quad = || pmf::make_quad(pmf::PolyVector::unit_y(), pmf::PolyVector::unit(), pmf::PolyVector::zero(), pmf::PolyVector::unit_x(), pmf::PolyColor::green()).geometry;
+    ///
+    /// // A rotated, pivoted, scaled part holding a scaled child
+    /// let mut inner = TransPolyMeshPtr::new("/inner".to_string(), PolyMesh::new(MeshType::Geometry, quad()), Some(pmf::PolyVector::unit_x()));
+    /// inner.transform.scale = Some(pmf::PolyVector { x: 3.0, y: 0.5, z: 1.0 });
+    /// let mut outer = TransPolyMeshPtr::new("/outer".to_string(), PolyMesh::new(MeshType::GeoGroup, quad()), Some(pmf::PolyVector::unit_z()));
+    /// outer.mesh.add_child(inner);
+    /// outer.transform.rotation = Some(PolyQuaternion::from_axis_angle(pmf::PolyVector::unit_y(), 0.7));
+    /// outer.transform.pivot = Some(pmf::PolyVector::unit() * 3.0);
+    /// outer.transform.scale = Some(pmf::PolyVector::unit() * 2.0);
+    /// let mut root = PolyMesh::new(MeshType::Group, None);
+    /// root.add_child(outer);
+    ///
+    /// // Freezing the part leaves every flattened vertex where it was
+    /// let before = pmf::get_flat_geometry(root.clone());
+    /// root.reset_transform_preserving_children("/outer").unwrap();
+    /// assert!(root.children[0].transform.is_identity(0.0));
+    /// let after = pmf::get_flat_geometry(root.clone());
+    /// assert_eq!(before.len(), after.len());
+    /// for (before, after) in before.iter().zip(after.iter()) {
+    ///     for (a, b) in before.triangles.iter().flatten().flatten().zip(after.triangles.iter().flatten().flatten()) {
+    ///         let offset = *a - *b;
+    ///         assert!(offset.dot(offset).sqrt() < 1e-5);
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// A rotated child under a non-uniform scale is rejected, and nothing changes:
+    ///
+    /// ```rust
+    /// use libpolymesh::prelude as pmf;
+    /// use libpolymesh::common::{PolyMesh, PolyMeshError, MeshType, TransPolyMeshPtr, transform::PolyQuaternion};
+    ///
+    /// let mut inner = TransPolyMeshPtr::new("/inner".to_string(), PolyMesh::new(MeshType::Group, None), None);
+    /// inner.transform.rotation = Some(PolyQuaternion::from_axis_angle(pmf::PolyVector::unit_z(), 0.4));
+    /// let mut outer = TransPolyMeshPtr::new("/outer".to_string(), PolyMesh::new(MeshType::Group, None), None);
+    /// outer.mesh.add_child(inner);
+    /// outer.transform.scale = Some(pmf::PolyVector { x: 2.0, y: 1.0, z: 1.0 });
+    /// let mut root = PolyMesh::new(MeshType::Group, None);
+    /// root.add_child(outer);
+    ///
+    /// let original = root.clone();
+    /// assert!(matches!(root.reset_transform_preserving_children("/outer"), Err(PolyMeshError::UnrepresentableTransform(_))));
+    /// assert!(root == original);
+    /// ```
+    pub fn reset_transform_preserving_children(&mut self, path: &str) -> Result<(), PolyMeshError> {
+        let indices = self.resolve_path_indices(path).ok_or_else(|| PolyMeshError::UnresolvedPath(path.to_string()))?;
+        let (last, parents) = match indices.split_last() {
+            Some(split) => split,
+            None => return Ok(())
+        };
+        let mut parent = self;
+        for i in parents {
+            parent = &mut parent.children[*i].mesh;
+        }
+        let node = &mut parent.children[*last];
+        let removed = node.transform;
+
+        // Make sure every child can take on the transform before changing anything
+        let scale = removed.get_scale();
+        let is_uniform = scale.x == scale.y && scale.y == scale.z;
+        let is_rotated = |child: &TransPolyMeshPtr| child.transform.rotation.is_some_and(|rotation| rotation.x != 0.0 || rotation.y != 0.0 || rotation.z != 0.0);
+        if !is_uniform && node.mesh.children.iter().any(is_rotated) {
+            return Err(PolyMeshError::UnrepresentableTransform(path.to_string()));
+        }
+
+        // Push the transform into the geometry and children
+        if let Some(geometry) = node.mesh.geometry.as_mut() {
+            *geometry = geometry.transformed_by_matrix(&removed.to_matrix());
+        }
+        for child in node.mesh.children.iter_mut() {
+            child.transform = child.transform.compose(&removed);
+        }
+        node.transform = PolyTransform::identity();
+        Ok(())
+    }
+
+    /// Show or hide the mesh at `path`. Hidden meshes stay in the hierarchy, but they and everything under them are skipped when flattening
+    pub fn set_visible(&mut self, path: &str, visible: bool) -> Result<(), PolyMeshError> {
+        match self.get_mesh_mut(path) {
+            Some(mesh) => {
+                mesh.visible = visible;
+                Ok(())
+            },
+            None => Err(PolyMeshError::UnresolvedPath(path.to_string()))
+        }
+    }
+
+    /// Remove all geometry from this mesh and everything under it, keeping names, transforms, and metadata.
+    ///
+    /// `Geometry` and `GeoGroup` meshes become `Group` meshes, or `Locator` meshes if they have no children
+    pub fn strip_geometry(&mut self) {
+        self.geometry = None;
+        if self.mesh_type == MeshType::Geometry || self.mesh_type == MeshType::GeoGroup {
+            self.mesh_type = if self.children.is_empty() { MeshType::Locator } else { MeshType::Group };
+        }
+        for child in self.children.iter_mut() {
+            child.mesh.strip_geometry();
+        }
+    }
+
+    /// Move everything under this mesh so the origin of the mesh at `path` ends up at the origin of this mesh.
+    ///
+    /// The root has no transform of its own, so this is done by offsetting the translation of every top-level child.
+    /// Geometry stored directly on the root does not move
+    pub fn recenter_on(&mut self, path: &str) -> Result<(), PolyMeshError> {
+        let offset = match self.world_matrix(path) {
+            Some(matrix) => matrix.transform_point(PolyVector::zero()),
+            None => return Err(PolyMeshError::UnresolvedPath(path.to_string()))
         };
+
+        // Shift every top-level child
+        for child in self.children.iter_mut() {
+            child.transform.translation = Some(child.transform.get_translation() - offset);
+        }
+        Ok(())
+    }
+
+    fn mirror_recursive(&mut self, axis: Axis) {
+        if let Some(geometry) = self.geometry.as_mut() {
+            geometry.mirror(axis);
+        }
+        for child in self.children.iter_mut() {
+            child.transform = child.transform.mirrored(axis);
+            child.mesh.mirror_recursive(axis);
+        }
+    }
+
+    /// Find every descendant of this mesh that has the metadata field `key`, along with its full path
+    pub fn find_by_metadata(&self, key: &str) -> Vec<(String, &PolyMesh)> {
+        let mut found = Vec::new();
+        self.find_recursive("", &|mesh| mesh.metadata.contains_key(key), &mut found);
+        found
+    }
+
+    /// Find every descendant of this mesh that has the metadata field `key` set to exactly `value`, along with its full path
+    pub fn find_by_metadata_value(&self, key: &str, value: &str) -> Vec<(String, &PolyMesh)> {
+        let mut found = Vec::new();
+        self.find_recursive("", &|mesh| mesh.metadata.get(key).map(String::as_str) == Some(value), &mut found);
+        found
+    }
+
+    fn find_recursive<'a>(&'a self, path: &str, predicate: &dyn Fn(&PolyMesh) -> bool, found: &mut Vec<(String, &'a PolyMesh)>) {
+        for child in &self.children {
+
+            // Build the full path to this child
+            let child_path = format!("{}{}", path, child.path);
+
+            // Check the child, then search its children
+            if predicate(&child.mesh) {
+                found.push((child_path.to_string(), child.mesh.as_ref()));
+            }
+            child.mesh.find_recursive(&child_path, predicate, found);
+        }
+    }
+
+    /// Rename the metadata field `old` to `new` on this mesh and every descendant that has it.
+    ///
+    /// A mesh that already has a `new` field keeps both fields untouched. Returns `(renamed, conflicts)`, counting
+    /// the meshes that were renamed and the meshes skipped because of an existing `new` field
+    pub fn rename_metadata_recursive(&mut self, old: &str, new: &str) -> (usize, usize) {
+        let mut counts = (0, 0);
+        if old != new && self.metadata.contains_key(old) {
+            if self.metadata.contains_key(new) {
+                counts.1 += 1;
+            } else if let Some(value) = self.metadata.remove(old) {
+                self.metadata.insert(new.to_string(), value);
+                counts.0 += 1;
+            }
+        }
+
+        // Rename throughout the children
+        for child in self.children.iter_mut() {
+            let (renamed, conflicts) = child.mesh.rename_metadata_recursive(old, new);
+            counts.0 += renamed;
+            counts.1 += conflicts;
+        }
+        counts
+    }
+
+    /// Get the bounding box of this mesh and all of its children in this mesh's local space, as `(min, max)`.
+    ///
+    /// Deferred placeholders that have not been loaded yet contribute their stored bounds
+    pub fn bounds(&self) -> Option<(PolyVector, PolyVector)> {
+        let own_bounds = self.geometry.as_ref().and_then(MeshDef::bounds);
+        let deferred_bounds = self.deferred_source.as_ref().and_then(|source| source.bounds);
+        let child_bounds = self.children.iter().filter_map(TransPolyMeshPtr::transformed_bounds);
+        bounds_of_points(own_bounds.into_iter().chain(deferred_bounds).chain(child_bounds).flat_map(|(min, max)| vec![min, max]))
     }
 
     /// Check if the mesh contains geometry
     pub fn contains_geometry(&self) -> bool {
+        if self.mesh_type == MeshType::Locator {
+            return false;
+        }
         return self.mesh_type == MeshType::Geometry || self.mesh_type == MeshType::GeoGroup || self.geometry.is_some();
     }
 
+    /// Check if the mesh contains geometry with at least one triangle.
+    ///
+    /// Unlike `contains_geometry`, this is false for geometry nodes whose `MeshDef` is missing or empty
+    pub fn has_renderable_geometry(&self) -> bool {
+        self.contains_geometry() && self.geometry.as_ref().and_then(|geometry| geometry.triangles.as_ref()).is_some_and(|triangles| !triangles.is_empty())
+    }
+
 }
\ No newline at end of file
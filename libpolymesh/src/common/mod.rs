@@ -1,10 +1,14 @@
 pub mod mesh;
 pub mod transform;
 pub mod serialization;
+pub mod error;
+pub mod geometry;
 
 pub use mesh::{
     PolyMesh,
     MeshType,
-    TransPolyMeshPtr
+    TransPolyMeshPtr,
+    DeferredSource
 };
-pub use serialization::data::mesh::MeshDef;
\ No newline at end of file
+pub use serialization::data::mesh::MeshDef;
+pub use error::PolyMeshError;
\ No newline at end of file
@@ -0,0 +1,94 @@
+//! Serde helpers for storing binary blobs as base64 strings in JSON
+
+use serde::{
+    Deserialize,
+    Deserializer,
+    Serializer,
+    ser::SerializeMap,
+    de::Error
+};
+use std::collections::{
+    BTreeMap,
+    HashMap
+};
+
+const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Encode bytes as padded, standard-alphabet base64
+///
+/// ```rust
+/// use libpolymesh::common::serialization::base64;
+///
+/// assert_eq!(base64::encode(b"pmf!"), "cG1mIQ==");
+/// assert_eq!(base64::decode("cG1mIQ==").unwrap(), b"pmf!");
+/// ```
+pub fn encode(data: &[u8]) -> String {
+    let mut output = String::with_capacity(data.len().div_ceil(3) * 4);
+
+    for chunk in data.chunks(3) {
+
+        // Pack the chunk into a 24 bit group
+        let group = (chunk[0] as u32) << 16
+            | (*chunk.get(1).unwrap_or(&0) as u32) << 8
+            | *chunk.get(2).unwrap_or(&0) as u32;
+
+        // Emit one character per 6 bits, padding any missing bytes
+        for i in 0..4 {
+            if i <= chunk.len() {
+                output.push(ALPHABET[(group >> (18 - i * 6)) as usize & 0x3f] as char);
+            } else {
+                output.push('=');
+            }
+        }
+    }
+
+    output
+}
+
+/// Decode padded, standard-alphabet base64
+pub fn decode(data: &str) -> Result<Vec<u8>, String> {
+    let data = data.trim_end_matches('=').as_bytes();
+    let mut output = Vec::with_capacity(data.len() * 3 / 4);
+
+    for chunk in data.chunks(4) {
+        if chunk.len() == 1 {
+            return Err("Invalid base64 length".to_string());
+        }
+
+        // Unpack the characters into a 24 bit group
+        let mut group = 0u32;
+        for (i, character) in chunk.iter().enumerate() {
+            let value = match ALPHABET.iter().position(|c| c == character) {
+                Some(value) => value as u32,
+                None => return Err(format!("Invalid base64 character: {}", *character as char))
+            };
+            group |= value << (18 - i * 6);
+        }
+
+        // Each extra character after the first carries one byte
+        for i in 0..chunk.len() - 1 {
+            output.push((group >> (16 - i * 8)) as u8);
+        }
+    }
+
+    Ok(output)
+}
+
+/// Serialize a map of binary blobs as a map of base64 strings, with the keys in sorted order
+pub fn serialize<S: Serializer>(blobs: &HashMap<String, Vec<u8>>, serializer: S) -> Result<S::Ok, S::Error> {
+    let mut map = serializer.serialize_map(Some(blobs.len()))?;
+    for (key, value) in blobs.iter().collect::<BTreeMap<_, _>>() {
+        map.serialize_entry(key, &encode(value))?;
+    }
+    map.end()
+}
+
+/// Deserialize a map of base64 strings into a map of binary blobs
+pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<HashMap<String, Vec<u8>>, D::Error> {
+    let encoded: HashMap<String, String> = HashMap::deserialize(deserializer)?;
+    let mut blobs = HashMap::new();
+    for (key, value) in encoded {
+        blobs.insert(key, decode(&value).map_err(D::Error::custom)?);
+    }
+    Ok(blobs)
+}
@@ -0,0 +1,34 @@
+//! Byte-stable JSON output, for comparing files against known-good copies
+
+use serde::Serialize;
+use serde_json::{
+    Map,
+    Value
+};
+
+/// Serialize `value` as canonical JSON: every object's keys in sorted order, two-space indentation, `\n` line endings
+/// (including a final one), and each number in its shortest form that reads back exactly (ex: `0.1` rather than `0.10000000149011612`).
+///
+/// The same data always gives the same bytes, no matter which order its maps were filled in or which platform wrote it
+pub(crate) fn to_canonical_json<T: Serialize>(value: &T) -> String {
+
+    // Going through text keeps each f32 in its own shortest form, which converting straight to a `Value` (as f64) would lose
+    let text = serde_json::to_string(value).expect("PolyMesh data always serializes to JSON");
+    let value: Value = serde_json::from_str(&text).expect("serde_json always reads back its own output");
+    let mut output = serde_json::to_string_pretty(&sort_keys(value)).expect("A JSON value always serializes");
+    output.push('\n');
+    output
+}
+
+/// Rebuild every object in `value` with its keys in sorted order, whatever order `Map` itself keeps
+fn sort_keys(value: Value) -> Value {
+    match value {
+        Value::Object(map) => {
+            let mut entries: Vec<(String, Value)> = map.into_iter().collect();
+            entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+            Value::Object(entries.into_iter().map(|(key, value)| (key, sort_keys(value))).collect::<Map<String, Value>>())
+        },
+        Value::Array(values) => Value::Array(values.into_iter().map(sort_keys).collect()),
+        value => value
+    }
+}
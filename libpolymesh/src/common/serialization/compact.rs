@@ -0,0 +1,120 @@
+//! Serde helpers for storing vectors, rotations, and matrices as flat JSON arrays (ex: `[x, y, z]`).
+//!
+//! Each helper also reads the older object form (ex: `{"x": 1.0, "y": 0.0, "z": 0.0}`), so existing files keep loading
+
+use crate::common::transform::{
+    PolyVector,
+    PolyQuaternion,
+    PolyMatrix4
+};
+use serde::{
+    Deserialize,
+    Deserializer,
+    Serialize,
+    Serializer
+};
+
+/// Either form of a stored vector
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum VectorRepr {
+    Array([f32; 3]),
+    Object(PolyVector)
+}
+
+impl From<VectorRepr> for PolyVector {
+    fn from(repr: VectorRepr) -> Self {
+        match repr {
+            VectorRepr::Array([x, y, z]) => PolyVector { x, y, z },
+            VectorRepr::Object(vector) => vector
+        }
+    }
+}
+
+/// Either form of a stored rotation
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum QuaternionRepr {
+    Array([f32; 4]),
+    Object(PolyQuaternion)
+}
+
+impl From<QuaternionRepr> for PolyQuaternion {
+    fn from(repr: QuaternionRepr) -> Self {
+        match repr {
+            QuaternionRepr::Array([x, y, z, w]) => PolyQuaternion { x, y, z, w },
+            QuaternionRepr::Object(quaternion) => quaternion
+        }
+    }
+}
+
+/// Store a `PolyVector` as `[x, y, z]`
+pub mod vector {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(vector: &PolyVector, serializer: S) -> Result<S::Ok, S::Error> {
+        [vector.x, vector.y, vector.z].serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<PolyVector, D::Error> {
+        VectorRepr::deserialize(deserializer).map(PolyVector::from)
+    }
+}
+
+/// Store an `Option<PolyVector>` as `[x, y, z]` or `null`
+pub mod optional_vector {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(vector: &Option<PolyVector>, serializer: S) -> Result<S::Ok, S::Error> {
+        vector.map(|vector| [vector.x, vector.y, vector.z]).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<PolyVector>, D::Error> {
+        Option::<VectorRepr>::deserialize(deserializer).map(|repr| repr.map(PolyVector::from))
+    }
+}
+
+/// Store an `Option<PolyQuaternion>` as `[x, y, z, w]` or `null`
+pub mod optional_quaternion {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(quaternion: &Option<PolyQuaternion>, serializer: S) -> Result<S::Ok, S::Error> {
+        quaternion.map(|quaternion| [quaternion.x, quaternion.y, quaternion.z, quaternion.w]).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<PolyQuaternion>, D::Error> {
+        Option::<QuaternionRepr>::deserialize(deserializer).map(|repr| repr.map(PolyQuaternion::from))
+    }
+}
+
+/// Store an optional `(min, max)` bounding box as `[[x, y, z], [x, y, z]]` or `null`
+pub mod optional_bounds {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(bounds: &Option<(PolyVector, PolyVector)>, serializer: S) -> Result<S::Ok, S::Error> {
+        bounds.map(|(min, max)| [[min.x, min.y, min.z], [max.x, max.y, max.z]]).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<(PolyVector, PolyVector)>, D::Error> {
+        Option::<(VectorRepr, VectorRepr)>::deserialize(deserializer).map(|repr| repr.map(|(min, max)| (min.into(), max.into())))
+    }
+}
+
+/// Store a `PolyMatrix4` as a flat, row-major array of 16 numbers
+pub mod matrix {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(matrix: &PolyMatrix4, serializer: S) -> Result<S::Ok, S::Error> {
+        let flat: Vec<f32> = matrix.m.iter().flatten().copied().collect();
+        flat.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<PolyMatrix4, D::Error> {
+        let flat = <[f32; 16]>::deserialize(deserializer)?;
+        let mut output = PolyMatrix4::identity();
+        for (i, value) in flat.iter().enumerate() {
+            output.m[i / 4][i % 4] = *value;
+        }
+        Ok(output)
+    }
+}
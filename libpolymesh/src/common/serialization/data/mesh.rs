@@ -1,13 +1,33 @@
 use super::super::super::{
     transform::{
         PolyVector,
-        PolyColor
+        PolyColor,
+        PolyMatrix4,
+        Axis
     },
-    TransPolyMeshPtr
+    TransPolyMeshPtr,
+    PolyMeshError
 };
 use serde::{Deserialize, Serialize};
 use serde_json::Result;
+use super::super::{
+    canonical::to_canonical_json,
+    text::read_json
+};
 use std::fs;
+use std::io::{
+    Read,
+    Write
+};
+use std::path::Path;
+
+/// Get the axis-aligned bounding box of a set of points as `(min, max)`, or `None` if there are no points
+pub fn bounds_of_points<I: IntoIterator<Item = PolyVector>>(points: I) -> Option<(PolyVector, PolyVector)> {
+    points.into_iter().fold(None, |bounds, point| match bounds {
+        Some((min, max)) => Some((PolyVector::component_min(min, point), PolyVector::component_max(max, point))),
+        None => Some((point, point))
+    })
+}
 
 /// Definition of a mesh, and its geometry
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
@@ -16,70 +36,215 @@ pub struct MeshDef {
     /// Color of the mesh
     pub color: PolyColor,
 
-    /// Triangle geometry
-    pub triangles: Option<Vec<[PolyVector;3]>>
+    /// Triangle geometry. Triangles do not share vertices, so every triangle carries its own three corners, and no normals
+    /// are stored alongside them
+    pub triangles: Option<Vec<[PolyVector;3]>>,
+
+    /// Hint that both sides of every triangle should be rendered (ex: foliage, cloth)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub double_sided: bool,
+
+    /// Optional name of the material to render this mesh with, resolved by the renderer
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub material: Option<String>,
+
+    /// Optional skinning joint indices, with one entry per triangle corner (3 per triangle, in the order of `triangles`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub joints: Option<Vec<[u16; 4]>>,
+
+    /// Optional skinning weights matching `joints`, with one entry per triangle corner
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub weights: Option<Vec<[f32; 4]>>,
+
+    /// Optional texture coordinates as `[u, v]`, with one entry per triangle corner
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub uvs: Option<Vec<[f32; 2]>>
 
 }
 
 impl MeshDef {
 
     /// Read a MeshDef from a file
-    pub fn from_file(file_path: &str) -> Result<MeshDef> {
-        // Read the file
-        let file_contents = fs::read_to_string(file_path).unwrap();
-        let mesh_def: MeshDef = serde_json::from_str(&file_contents.to_string()).unwrap();
+    pub fn from_file<P: AsRef<Path>>(file_path: P) -> Result<MeshDef> {
+        MeshDef::from_reader(fs::File::open(file_path).map_err(serde_json::Error::io)?)
+    }
 
-        Ok(mesh_def)
+    /// Read a MeshDef object from any reader, skipping a leading UTF-8 byte order mark
+    ///
+    /// ```rust
+    /// use libpolymesh::common::MeshDef;
+    ///
+    /// let json = b"\xEF\xBB\xBF{\"color\": {\"r\": 0, \"g\": 255, \"b\": 0, \"a\": 255}, \"triangles\": []}";
+    /// assert!(MeshDef::from_reader(&json[..]).is_ok());
+    /// ```
+    pub fn from_reader<R: Read>(reader: R) -> Result<MeshDef> {
+        read_json(reader)
     }
 
-    /// Check if this mesh can be culled by another mesh
-    pub fn culled_by(&self, other: &MeshDef) -> bool {
+    /// Write this MeshDef as compact JSON to any writer
+    pub fn to_writer<W: Write>(&self, writer: W) -> Result<()> {
+        serde_json::to_writer(writer, self)
+    }
 
-        if self.triangles.is_some() && other.triangles.is_some() {
-            return self.triangles.as_ref() == other.triangles.as_ref();
+    /// Get this MeshDef as canonical JSON, which is byte-for-byte the same every time for the same data (ex: for golden file tests).
+    ///
+    /// Keys are sorted, indentation and line endings are fixed, and numbers use their shortest exact form
+    pub fn to_canonical_json(&self) -> String {
+        to_canonical_json(self)
+    }
+
+    /// Check if this mesh carries both skinning joints and weights
+    pub fn is_skinned(&self) -> bool {
+        self.joints.is_some() && self.weights.is_some()
+    }
+
+    /// Swap the skinning data and texture coordinates of the last two corners of the triangle at `index`, to follow a reversed winding
+    pub(crate) fn swap_corner_attributes(&mut self, index: usize) {
+        if let Some(uvs) = self.uvs.as_mut().filter(|uvs| uvs.len() >= index * 3 + 3) {
+            uvs.swap(index * 3 + 1, index * 3 + 2);
+        }
+        if let Some(joints) = self.joints.as_mut().filter(|joints| joints.len() >= index * 3 + 3) {
+            joints.swap(index * 3 + 1, index * 3 + 2);
         }
+        if let Some(weights) = self.weights.as_mut().filter(|weights| weights.len() >= index * 3 + 3) {
+            weights.swap(index * 3 + 1, index * 3 + 2);
+        }
+    }
 
-        return false;
+    /// Check that every vertex in this mesh is finite
+    pub fn is_finite(&self) -> bool {
+        match &self.triangles {
+            Some(triangles) => triangles.iter().all(|triangle| triangle.iter().all(PolyVector::is_finite)),
+            None => true
+        }
+    }
 
+    /// Snap every vertex component to the nearest multiple of `grid`.
+    ///
+    /// This is lossy, but makes the mesh far more compressible. Applying the same grid twice has no further effect.
+    /// Fails with `PolyMeshError::InvalidDimensions`, leaving the mesh untouched, if `grid` is not a positive finite number
+    pub fn quantize(&mut self, grid: f32) -> std::result::Result<(), PolyMeshError> {
+        if !grid.is_finite() || grid <= 0.0 {
+            return Err(PolyMeshError::InvalidDimensions(format!("Quantization grid must be positive, got {}", grid)));
+        }
+        if let Some(triangles) = self.triangles.as_mut() {
+            for triangle in triangles.iter_mut() {
+                for point in triangle.iter_mut() {
+                    *point = point.quantized(grid);
+                }
+            }
+        }
+        Ok(())
     }
 
-    /// Copy this mesh to be absolutely transformed by its parent (used in rendering mostly)
-    pub fn transformed_by(&self, parent: &TransPolyMeshPtr) -> Self {
+    /// Get the axis-aligned bounding box of this mesh as `(min, max)`, or `None` if it has no vertices
+    pub fn bounds(&self) -> Option<(PolyVector, PolyVector)> {
+        bounds_of_points(self.triangles.as_ref()?.iter().flatten().copied())
+    }
 
-        // Handle triangles
-        let mut triangles = None;
-        if self.triangles.is_some() {
-
-            // Build new triangle list
-            let mut tmp_triangles: Vec<[PolyVector;3]> = Vec::new();
-
-            for triangle in self.triangles.as_ref().unwrap() {
-                
-                // Build new point list
-                let mut vecs = [PolyVector { x:0.0, y:0.0, z:0.0 }; 3];
-
-                // Add transformed points
-                for i in 0..3 {
-                    if parent.translation.is_some() {
-                        vecs[i] = triangle[i] + *parent.translation.as_ref().unwrap();
-                    } else {
-                        vecs[i] = triangle[i];
-                    }
+    /// Scale every vertex of this mesh component-wise by `factor`, about the origin.
+    ///
+    /// Face normals are implied by triangle winding, so they stay perpendicular to the scaled surface without any extra work.
+    /// A scale that flips an odd number of axes turns the mesh inside-out, in which case the winding is reversed to compensate
+    ///
+    /// ```rust
+    /// use libpolymesh::prelude as pmf;
+    /// use libpolymesh::common::MeshDef;
+    ///
+    /// // A triangle tilted 45 degrees, facing (1, 0, -1)
+    /// let corners = [pmf::PolyVector::zero(), pmf::PolyVector::unit_y(), pmf::PolyVector { x: 1.0, y: 0.0, z: 1.0 }];
+    /// for factor in [pmf::PolyVector { x: 2.0, y: 1.0, z: 1.0 }, pmf::PolyVector { x: -2.0, y: 1.0, z: 1.0 }] {
+    ///     let mut mesh = MeshDef { color: pmf::PolyColor::green(), triangles: Some(vec![corners]), double_sided: false, material: None, joints: None, weights: None, uvs: None };
+    ///     mesh.scale(factor);
+    ///
+    ///     // The normal stays perpendicular to the stretched surface, and on the same side of it
+    ///     let [a, b, c] = mesh.triangles.as_ref().unwrap()[0];
+    ///     let normal = mesh.smooth_normals()[0][0];
+    ///     assert!(normal.dot(b - a).abs() < 1e-6 && normal.dot(c - a).abs() < 1e-6);
+    ///     let expected = pmf::PolyVector { x: 1.0 / factor.x, y: 0.0, z: -1.0 };
+    ///     assert!(normal.dot(expected) > 0.0);
+    /// }
+    /// ```
+    pub fn scale(&mut self, factor: PolyVector) {
+        let flips_winding = factor.x * factor.y * factor.z < 0.0;
+        let mut count = 0;
+        if let Some(triangles) = self.triangles.as_mut() {
+            count = triangles.len();
+            for triangle in triangles.iter_mut() {
+                for point in triangle.iter_mut() {
+                    *point = point.component_mul(factor);
+                }
+                if flips_winding {
+                    triangle.swap(1, 2);
                 }
+            }
+        }
+        if flips_winding {
+            (0..count).for_each(|index| self.swap_corner_attributes(index));
+        }
+    }
 
-                tmp_triangles.push(vecs);
+    /// Mirror this mesh across the plane perpendicular to `axis`, through the origin.
+    ///
+    /// Mirroring turns the mesh inside-out, so the winding of every triangle is reversed to keep its faces pointing outward
+    pub fn mirror(&mut self, axis: Axis) {
+        let mut count = 0;
+        if let Some(triangles) = self.triangles.as_mut() {
+            count = triangles.len();
+            for triangle in triangles.iter_mut() {
+                *triangle = [triangle[0].mirrored(axis), triangle[2].mirrored(axis), triangle[1].mirrored(axis)];
             }
+        }
+        (0..count).for_each(|index| self.swap_corner_attributes(index));
+    }
 
-            triangles = Some(tmp_triangles);
+    /// Check if this mesh can be culled by another mesh
+    pub fn culled_by(&self, other: &MeshDef) -> bool {
+
+        if self.triangles.is_some() && other.triangles.is_some() {
+            return self.triangles.as_ref() == other.triangles.as_ref();
         }
 
-        Self {
-            triangles, 
+        return false;
+
+    }
+
+    /// Copy this mesh to be absolutely transformed by its parent (used in rendering mostly).
+    ///
+    /// The parent's full transform (translation, rotation, scale, and pivot) is applied through `transformed_by_matrix`
+    pub fn transformed_by(&self, parent: &TransPolyMeshPtr) -> Self {
+        self.transformed_by_matrix(&parent.transform.to_matrix())
+    }
+
+    /// Copy this mesh with every vertex transformed by `matrix`.
+    ///
+    /// If the matrix mirrors space (a negative determinant), the winding of every triangle is reversed so faces keep pointing outward.
+    /// Skinning data and texture coordinates are left as-is (apart from following the winding), since skinning is applied at render time
+    pub fn transformed_by_matrix(&self, matrix: &PolyMatrix4) -> Self {
+        let m = &matrix.m;
+        let determinant = m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+            - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+            + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0]);
+        let flips_winding = determinant < 0.0;
+
+        let triangles = self.triangles.as_ref().map(|triangles| triangles.iter().map(|triangle| {
+            let [a, b, c] = triangle.map(|point| matrix.transform_point(point));
+            if flips_winding { [a, c, b] } else { [a, b, c] }
+        }).collect());
+
+        let mut output = Self {
+            triangles,
             color: self.color,
-            // emission: self.emission,
-            // albedo: self.albedo
+            double_sided: self.double_sided,
+            material: self.material.clone(),
+            joints: self.joints.clone(),
+            weights: self.weights.clone(),
+            uvs: self.uvs.clone()
+        };
+        if flips_winding {
+            (0..output.triangles.as_ref().map_or(0, Vec::len)).for_each(|index| output.swap_corner_attributes(index));
         }
-
+        output
     }
 
 }
\ No newline at end of file
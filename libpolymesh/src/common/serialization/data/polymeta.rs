@@ -1,13 +1,30 @@
 use super::super::super::{
     mesh::MeshType,
-    transform::PolyVector
+    transform::{
+        PolyTransform,
+        PolyVector
+    }
 };
+use super::mesh::MeshDef;
 use std::collections::HashMap;
 use serde::{Deserialize, Serialize};
-use serde_json::Result;
+use serde_json::{
+    Map,
+    Result,
+    Value
+};
+use super::super::{
+    canonical::to_canonical_json,
+    text::read_json
+};
 use std::fs;
+use std::io::{
+    Read,
+    Write
+};
+use std::path::Path;
 
-pub const LATEST_POLY_META_VERSION: f32 = 1.1;
+pub const LATEST_POLY_META_VERSION: f32 = 1.2;
 
 /// A JSON reference to another object.
 /// 
@@ -17,26 +34,27 @@ pub const LATEST_POLY_META_VERSION: f32 = 1.1;
 ///
 /// ```json
 /// {
-///     path": "/faces_gr/positive_x_geo",
-///     "translation": {
-///         "x":1.0,
-///         "y":0.0,
-///         "z":0.0
-///     }   
+///     "path": "/faces_gr/positive_x_geo",
+///     "translation": [1.0, 0.0, 0.0]
 /// }
 /// ```
 ///
 /// ```rust
 /// use libpolymesh::prelude as pmf;
-/// use libpolymesh::common::serialization::data::polymeta::PolyChildReference;
+/// use libpolymesh::common::serialization::data::polymeta::{
+///     PolyChildReference,
+///     PathMode
+/// };
 ///
 /// let reference = PolyChildReference {
 ///     path: "/faces_gr/positive_x_geo".to_string(),
-///     translation: Some(pmf::PolyVector {
+///     transform: pmf::PolyTransform::from_translation(pmf::PolyVector {
 ///         x: 1.0,
 ///         y: 0.0,
 ///         z: 0.0,
-///     })
+///     }),
+///     path_mode: PathMode::RelativeToParent,
+///     deferred: false
 /// };
 /// ```
 /// 
@@ -44,16 +62,69 @@ pub const LATEST_POLY_META_VERSION: f32 = 1.1;
 /// children. You can think of a `PolyChildReference` as an "include statement, with metadata". 
 ///
 /// ### Some notes
-///  - The translation is applied as a transform on everything under and including the child
-///  - The path is relative to the `polymeta.json` that defines it, technically meaning meshes above the parent can be used as children (please don't do this)
+///  - The transform is applied to everything under and including the child. Its `translation`, `rotation`, `scale`, and `pivot` are stored directly on the child element, and all but `translation` may be omitted. Files older than version 1.2 store these as objects (ex: `{"x": 1.0, "y": 0.0, "z": 0.0}`), which are still accepted
+///  - The path is relative to the `polymeta.json` that defines it (or to the scene root, if `path_mode` is `RelativeToRoot`), technically meaning meshes above the parent can be used as children (please don't do this)
+///
+/// ```rust
+/// use libpolymesh::common::serialization::data::polymeta::PolyChildReference;
+///
+/// // The same child, as written before and after version 1.2
+/// let legacy: PolyChildReference = serde_json::from_str(r#"{
+///     "path": "/child",
+///     "translation": {"x": 1.0, "y": 2.0, "z": 3.0},
+///     "rotation": {"x": 0.0, "y": 0.0, "z": 0.70710677, "w": 0.70710677},
+///     "scale": {"x": 2.0, "y": 2.0, "z": 2.0}
+/// }"#).unwrap();
+/// let current: PolyChildReference = serde_json::from_str(r#"{
+///     "path": "/child",
+///     "translation": [1.0, 2.0, 3.0],
+///     "rotation": [0.0, 0.0, 0.70710677, 0.70710677],
+///     "scale": [2.0, 2.0, 2.0]
+/// }"#).unwrap();
+/// assert_eq!(legacy.transform, current.transform);
+/// assert_eq!(legacy.transform.get_translation().y, 2.0);
+/// assert_eq!(legacy.transform.get_rotation().w, 0.70710677);
+/// ```
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct PolyChildReference {
 
-    /// Path describing where to find the child. This is relative to the parent
+    /// Path describing where to find the child. This is relative to the parent, unless `path_mode` says otherwise
     pub path: String,
 
-    /// Possible translation to apply to the child. This applies to all meshes under the child by proxy
-    pub translation: Option<PolyVector>
+    /// Transform to apply to the child. This applies to all meshes under the child by proxy
+    #[serde(flatten)]
+    pub transform: PolyTransform,
+
+    /// What `path` is relative to. Only written when it is not the default, `RelativeToParent`
+    #[serde(default, skip_serializing_if = "PathMode::is_relative_to_parent")]
+    pub path_mode: PathMode,
+
+    /// If the child's geometry and children should only be loaded on request, leaving a placeholder with its stored
+    /// `bounds` until then (see `FlatPolyMesh::load_deferred`). Only written when `true`
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub deferred: bool
+
+}
+
+/// What the `path` of a `PolyChildReference` is relative to on disk
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[serde(rename_all = "PascalCase")]
+pub enum PathMode {
+
+    /// The directory holding the `polymeta.json` that lists the child
+    #[default]
+    RelativeToParent,
+
+    /// The directory holding the scene's root `polymeta.json`
+    RelativeToRoot
+
+}
+
+impl PathMode {
+
+    fn is_relative_to_parent(&self) -> bool {
+        *self == PathMode::RelativeToParent
+    }
 
 }
 
@@ -64,27 +135,108 @@ pub struct PolyMeta {
     /// PolyMeta version
     pub version: f32,
 
+    /// Optional stable identifier for this mesh, which survives renames and moves
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub uuid: Option<String>,
+
     /// Type of this mesh
     #[serde(rename = "type")]
     pub mesh_type: MeshType,
 
-    /// Arbitrary metadata
+    /// Arbitrary metadata. Keys are always written in sorted order to keep files stable across saves
+    #[serde(serialize_with = "super::super::sorted::serialize")]
     pub metadata: HashMap<String, String>,
 
+    /// Arbitrary binary data, stored as base64 strings
+    #[serde(default, skip_serializing_if = "HashMap::is_empty", with = "super::super::base64")]
+    pub blobs: HashMap<String, Vec<u8>>,
+
+    /// Arbitrary structured data, stored as plain JSON. Keys are always written in sorted order
+    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    pub extras: Map<String, Value>,
+
     /// Children of the mesh
-    pub children: Vec<PolyChildReference>
+    pub children: Vec<PolyChildReference>,
+
+    /// Optional free-form, possibly multi-line notes (ex: authoring comments). These are kept across saves, but never affect the mesh itself
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+
+    /// If this mesh and its children are included when flattening. Only written when `false`
+    #[serde(default = "default_visible", skip_serializing_if = "is_visible")]
+    pub visible: bool,
+
+    /// Optional size of one unit in meters (ex: 0.01 for centimeters). Only used on the root, where unset means 1.0.
+    /// Reading fails with `PolyMeshError::InvalidDimensions` if this is not a positive finite number
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unit_scale: Option<f32>,
+
+    /// Optional precomputed `(min, max)` bounding box of this mesh and everything under it, in its local space.
+    /// Written alongside the mesh so its size can be checked without reading any geometry. Use `bounds` to read it
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "super::super::compact::optional_bounds")]
+    pub bounds: Option<(PolyVector, PolyVector)>,
+
+    /// Optional inline geometry, stored under the `mesh` key.
+    ///
+    /// When present, this is used in place of a `mesh.json` file next to the `polymeta.json`.
+    /// If both exist, the inline geometry wins and the `mesh.json` file is ignored (with a warning)
+    #[serde(rename = "mesh", default, skip_serializing_if = "Option::is_none")]
+    pub geometry: Option<MeshDef>
+
+}
 
+fn default_visible() -> bool {
+    true
+}
+
+fn is_visible(visible: &bool) -> bool {
+    *visible
 }
 
 impl PolyMeta {
 
     /// Read a PolyMeta object from a file
-    pub fn from_file(file_path: &str) -> Result<PolyMeta> {
-        // Read the file
-        let file_contents = fs::read_to_string(file_path).unwrap();
-        let poly_meta: PolyMeta = serde_json::from_str(&file_contents.to_string()).unwrap();
+    pub fn from_file<P: AsRef<Path>>(file_path: P) -> Result<PolyMeta> {
+        PolyMeta::from_reader(fs::File::open(file_path).map_err(serde_json::Error::io)?)
+    }
+
+    /// Read a PolyMeta object from any reader, skipping a leading UTF-8 byte order mark
+    pub fn from_reader<R: Read>(reader: R) -> Result<PolyMeta> {
+        read_json(reader)
+    }
+
+    /// Write this PolyMeta as compact JSON to any writer
+    pub fn to_writer<W: Write>(&self, writer: W) -> Result<()> {
+        serde_json::to_writer(writer, self)
+    }
+
+    /// Get this PolyMeta as canonical JSON, which is byte-for-byte the same every time for the same data (ex: for golden file tests).
+    ///
+    /// Keys are sorted, indentation and line endings are fixed, and numbers use their shortest exact form
+    pub fn to_canonical_json(&self) -> String {
+        to_canonical_json(self)
+    }
+
+    /// Get the stored bounding box as `(min, max)`, or `None` if there is none and it must be computed from the geometry.
+    ///
+    /// Stored bounds with a NaN or infinite component are treated as missing
+    pub fn bounds(&self) -> Option<(PolyVector, PolyVector)> {
+        self.bounds.filter(|(min, max)| min.is_finite() && max.is_finite())
+    }
 
-        Ok(poly_meta)
+    /// Get the path of every child that is listed more than once with the same transform, once per path.
+    ///
+    /// Repeating a path with different transforms is how instancing works, so only exact repeats (which would render
+    /// the same child twice in the same spot) are reported
+    pub fn duplicate_children(&self) -> Vec<String> {
+        let mut duplicates: Vec<String> = Vec::new();
+        for (i, child) in self.children.iter().enumerate() {
+            let repeated = self.children[..i].iter().any(|earlier| earlier.path == child.path && earlier.transform == child.transform);
+            if repeated && !duplicates.contains(&child.path) {
+                duplicates.push(child.path.clone());
+            }
+        }
+        duplicates
     }
 
 }
\ No newline at end of file
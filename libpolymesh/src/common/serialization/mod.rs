@@ -1 +1,6 @@
-pub mod data;
\ No newline at end of file
+pub mod data;
+pub mod base64;
+pub mod sorted;
+pub mod compact;
+pub(crate) mod text;
+pub(crate) mod canonical;
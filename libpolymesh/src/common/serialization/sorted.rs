@@ -0,0 +1,15 @@
+//! Serde helper for writing maps with their keys in sorted order, so saving the same data always produces the same file
+
+use serde::{
+    Serialize,
+    Serializer
+};
+use std::collections::{
+    BTreeMap,
+    HashMap
+};
+
+/// Serialize a `HashMap` with its keys sorted
+pub fn serialize<S: Serializer, V: Serialize>(map: &HashMap<String, V>, serializer: S) -> Result<S::Ok, S::Error> {
+    map.iter().collect::<BTreeMap<_, _>>().serialize(serializer)
+}
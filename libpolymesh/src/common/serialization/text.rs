@@ -0,0 +1,114 @@
+//! Helpers for reading JSON text written by other tools
+
+use crate::common::PolyMeshError;
+use serde::de::DeserializeOwned;
+use std::fs::File;
+use std::io::{
+    self,
+    BufReader,
+    Read
+};
+use std::path::Path;
+
+/// The UTF-8 byte order mark, which some editors (mostly on Windows) write at the start of text files
+const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
+
+/// A reader that skips a leading UTF-8 byte order mark, and fails with `io::ErrorKind::InvalidData` once it reaches
+/// anything that is not UTF-8
+pub(crate) struct TextReader<R: Read> {
+    inner: R,
+
+    /// Bytes read from the start of `inner` while looking for a byte order mark, or `None` until that has happened
+    head: Option<Vec<u8>>,
+
+    /// The start of a character that was cut off at the end of the last read
+    partial: Vec<u8>
+}
+
+impl<R: Read> TextReader<R> {
+
+    pub(crate) fn new(inner: R) -> Self {
+        Self {
+            inner,
+            head: None,
+            partial: Vec::new()
+        }
+    }
+
+    /// Check that `bytes` continue the text read so far as valid UTF-8
+    fn check(&mut self, bytes: &[u8]) -> io::Result<()> {
+        let mut text = std::mem::take(&mut self.partial);
+        text.extend_from_slice(bytes);
+        if let Err(error) = std::str::from_utf8(&text) {
+
+            // A character cut off at the end may still be finished by the next read
+            if error.error_len().is_some() {
+                return Err(io::Error::new(io::ErrorKind::InvalidData, "text is not valid UTF-8"));
+            }
+            self.partial = text[error.valid_up_to()..].to_vec();
+        }
+        Ok(())
+    }
+
+}
+
+impl<R: Read> Read for TextReader<R> {
+
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+
+        // Look for a byte order mark before handing out anything
+        if self.head.is_none() {
+            let mut head = Vec::with_capacity(UTF8_BOM.len());
+            self.inner.by_ref().take(UTF8_BOM.len() as u64).read_to_end(&mut head)?;
+            if head == UTF8_BOM {
+                head.clear();
+            }
+            self.head = Some(head);
+        }
+
+        // Hand out whatever was read while looking, then carry on with the inner reader
+        let count = match self.head.as_mut().filter(|head| !head.is_empty()) {
+            Some(head) => {
+                let count = head.len().min(buf.len());
+                buf[..count].copy_from_slice(&head[..count]);
+                head.drain(..count);
+                count
+            },
+            None => self.inner.read(buf)?
+        };
+
+        // Running out in the middle of a character is also invalid
+        if count == 0 && !buf.is_empty() && !self.partial.is_empty() {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "text ends partway through a UTF-8 character"));
+        }
+        self.check(&buf[..count])?;
+        Ok(count)
+    }
+
+}
+
+/// Read JSON from any reader into any deserializable type, skipping a leading UTF-8 byte order mark.
+///
+/// Text that is not UTF-8 fails with an IO error of kind `io::ErrorKind::InvalidData`
+pub(crate) fn read_json<T: DeserializeOwned, R: Read>(reader: R) -> serde_json::Result<T> {
+    serde_json::from_reader(BufReader::new(TextReader::new(reader)))
+}
+
+/// Read a JSON file into any deserializable type, skipping a leading UTF-8 byte order mark.
+///
+/// Fails with `PolyMeshError::Encoding` if the file is not valid UTF-8, or `PolyMeshError::InvalidFile` if it can not be read or parsed
+pub(crate) fn read_json_file<T: DeserializeOwned>(file_path: &Path) -> Result<T, PolyMeshError> {
+    let path = file_path.display().to_string();
+    let file = File::open(file_path).map_err(|error| PolyMeshError::InvalidFile(path.clone(), serde_json::Error::io(error)))?;
+    read_json(file).map_err(|error| {
+        if !error.is_io() {
+            return PolyMeshError::InvalidFile(path, error);
+        }
+        let error = io::Error::from(error);
+        if error.kind() == io::ErrorKind::InvalidData {
+            PolyMeshError::Encoding(path)
+        } else {
+            PolyMeshError::InvalidFile(path, serde_json::Error::io(error))
+        }
+    })
+}
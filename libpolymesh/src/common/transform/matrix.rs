@@ -0,0 +1,179 @@
+use std::ops::Mul;
+use super::{
+    vector::PolyVector,
+    quaternion::PolyQuaternion
+};
+
+/// Simple 4x4 transformation matrix, stored in row-major order
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct PolyMatrix4 {
+    pub m: [[f32; 4]; 4]
+}
+
+impl PolyMatrix4 {
+
+    pub fn identity() -> Self {
+        Self {
+            m: [
+                [1.0, 0.0, 0.0, 0.0],
+                [0.0, 1.0, 0.0, 0.0],
+                [0.0, 0.0, 1.0, 0.0],
+                [0.0, 0.0, 0.0, 1.0]
+            ]
+        }
+    }
+
+    /// Create a matrix that translates points by `translation`
+    pub fn from_translation(translation: PolyVector) -> Self {
+        let mut output = Self::identity();
+        output.m[0][3] = translation.x;
+        output.m[1][3] = translation.y;
+        output.m[2][3] = translation.z;
+        output
+    }
+
+    /// Create a matrix that rotates points by `rotation`
+    pub fn from_rotation(rotation: PolyQuaternion) -> Self {
+        let PolyQuaternion { x, y, z, w } = rotation.normalized();
+        let mut output = Self::identity();
+        output.m[0][0] = 1.0 - 2.0 * (y * y + z * z);
+        output.m[0][1] = 2.0 * (x * y - z * w);
+        output.m[0][2] = 2.0 * (x * z + y * w);
+        output.m[1][0] = 2.0 * (x * y + z * w);
+        output.m[1][1] = 1.0 - 2.0 * (x * x + z * z);
+        output.m[1][2] = 2.0 * (y * z - x * w);
+        output.m[2][0] = 2.0 * (x * z - y * w);
+        output.m[2][1] = 2.0 * (y * z + x * w);
+        output.m[2][2] = 1.0 - 2.0 * (x * x + y * y);
+        output
+    }
+
+    /// Create a matrix that scales points component-wise by `scale`
+    pub fn from_scale(scale: PolyVector) -> Self {
+        let mut output = Self::identity();
+        output.m[0][0] = scale.x;
+        output.m[1][1] = scale.y;
+        output.m[2][2] = scale.z;
+        output
+    }
+
+    /// Split this matrix into a translation, rotation, and component-wise scale, applied in scale-rotate-translate order.
+    ///
+    /// The upper 3x3 is split with a polar decomposition, so a matrix built from a `PolyTransform` comes back exactly (up to rounding),
+    /// and one with shear gets the closest rotation and the stretch along each axis. A mirroring matrix gets a negative X scale.
+    /// Returns `None` if the matrix collapses space (ex: a zero scale), since no rotation can be recovered from it
+    pub fn decompose(&self) -> Option<(PolyVector, PolyQuaternion, PolyVector)> {
+        let linear = [
+            [self.m[0][0], self.m[0][1], self.m[0][2]],
+            [self.m[1][0], self.m[1][1], self.m[1][2]],
+            [self.m[2][0], self.m[2][1], self.m[2][2]]
+        ];
+        if !determinant3(&linear).is_normal() {
+            return None;
+        }
+
+        // Average the matrix with its inverse transpose until it settles on the nearest orthogonal matrix
+        let mut rotation = linear;
+        for _ in 0..32 {
+            let determinant = determinant3(&rotation);
+            let cofactors = cofactors3(&rotation);
+            let mut next = rotation;
+            let mut change: f32 = 0.0;
+            for row in 0..3 {
+                for col in 0..3 {
+                    next[row][col] = 0.5 * (rotation[row][col] + cofactors[row][col] / determinant);
+                    change = change.max((next[row][col] - rotation[row][col]).abs());
+                }
+            }
+            rotation = next;
+            if change < 1e-7 {
+                break;
+            }
+        }
+
+        // The stretch is what is left over once the rotation is undone
+        let stretch = |axis: usize| (0..3).map(|i| rotation[i][axis] * linear[i][axis]).sum::<f32>();
+        let mut scale = PolyVector { x: stretch(0), y: stretch(1), z: stretch(2) };
+
+        // A mirroring matrix leaves an improper rotation, so move the flip into the scale
+        if determinant3(&rotation) < 0.0 {
+            for row in rotation.iter_mut() {
+                row[0] = -row[0];
+            }
+            scale.x = -scale.x;
+        }
+
+        let translation = PolyVector { x: self.m[0][3], y: self.m[1][3], z: self.m[2][3] };
+        Some((translation, quaternion_from_rotation(&rotation), scale))
+    }
+
+    /// Transform a single point by this matrix
+    pub fn transform_point(&self, point: PolyVector) -> PolyVector {
+        let m = &self.m;
+        PolyVector {
+            x: m[0][0] * point.x + m[0][1] * point.y + m[0][2] * point.z + m[0][3],
+            y: m[1][0] * point.x + m[1][1] * point.y + m[1][2] * point.z + m[1][3],
+            z: m[2][0] * point.x + m[2][1] * point.y + m[2][2] * point.z + m[2][3]
+        }
+    }
+
+}
+
+impl Mul for PolyMatrix4 {
+    type Output = PolyMatrix4;
+
+    fn mul(self, other: PolyMatrix4) -> PolyMatrix4 {
+        let mut output = [[0.0; 4]; 4];
+        for (row, output_row) in output.iter_mut().enumerate() {
+            for (col, value) in output_row.iter_mut().enumerate() {
+                *value = (0..4).map(|i| self.m[row][i] * other.m[i][col]).sum();
+            }
+        }
+        PolyMatrix4 { m: output }
+    }
+}
+
+/// Transform every point in `points` in place by a single matrix
+pub fn transform_points(points: &mut [PolyVector], m: &PolyMatrix4) {
+    for point in points.iter_mut() {
+        *point = m.transform_point(*point);
+    }
+}
+
+fn determinant3(m: &[[f32; 3]; 3]) -> f32 {
+    m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+        - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+        + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0])
+}
+
+/// Get the cofactor matrix of `m`, which is its inverse transpose scaled by its determinant
+fn cofactors3(m: &[[f32; 3]; 3]) -> [[f32; 3]; 3] {
+    let mut output = [[0.0; 3]; 3];
+    for (row, output_row) in output.iter_mut().enumerate() {
+        for (col, value) in output_row.iter_mut().enumerate() {
+            let (r0, r1) = ((row + 1) % 3, (row + 2) % 3);
+            let (c0, c1) = ((col + 1) % 3, (col + 2) % 3);
+            *value = m[r0][c0] * m[r1][c1] - m[r0][c1] * m[r1][c0];
+        }
+    }
+    output
+}
+
+/// Convert a rotation matrix to a quaternion, starting from its largest diagonal term to stay numerically stable
+fn quaternion_from_rotation(m: &[[f32; 3]; 3]) -> PolyQuaternion {
+    let trace = m[0][0] + m[1][1] + m[2][2];
+    let rotation = if trace > 0.0 {
+        let s = (trace + 1.0).sqrt() * 2.0;
+        PolyQuaternion { x: (m[2][1] - m[1][2]) / s, y: (m[0][2] - m[2][0]) / s, z: (m[1][0] - m[0][1]) / s, w: s / 4.0 }
+    } else if m[0][0] > m[1][1] && m[0][0] > m[2][2] {
+        let s = (1.0 + m[0][0] - m[1][1] - m[2][2]).sqrt() * 2.0;
+        PolyQuaternion { x: s / 4.0, y: (m[0][1] + m[1][0]) / s, z: (m[0][2] + m[2][0]) / s, w: (m[2][1] - m[1][2]) / s }
+    } else if m[1][1] > m[2][2] {
+        let s = (1.0 + m[1][1] - m[0][0] - m[2][2]).sqrt() * 2.0;
+        PolyQuaternion { x: (m[0][1] + m[1][0]) / s, y: s / 4.0, z: (m[1][2] + m[2][1]) / s, w: (m[0][2] - m[2][0]) / s }
+    } else {
+        let s = (1.0 + m[2][2] - m[0][0] - m[1][1]).sqrt() * 2.0;
+        PolyQuaternion { x: (m[0][2] + m[2][0]) / s, y: (m[1][2] + m[2][1]) / s, z: s / 4.0, w: (m[1][0] - m[0][1]) / s }
+    };
+    rotation.normalized()
+}
@@ -1,5 +1,17 @@
 mod vector;
 mod color;
+mod matrix;
+mod quaternion;
+mod poly_transform;
 
-pub use vector::PolyVector;
-pub use color::PolyColor;
\ No newline at end of file
+pub use vector::{
+    PolyVector,
+    Axis
+};
+pub use color::PolyColor;
+pub use matrix::{
+    PolyMatrix4,
+    transform_points
+};
+pub use quaternion::PolyQuaternion;
+pub use poly_transform::PolyTransform;
\ No newline at end of file
@@ -0,0 +1,187 @@
+use serde::{Deserialize, Serialize};
+use crate::common::PolyMeshError;
+use super::{
+    vector::{
+        PolyVector,
+        Axis
+    },
+    quaternion::PolyQuaternion,
+    matrix::PolyMatrix4
+};
+
+/// A lightweight translation, rotation, and scale, applied in scale-rotate-translate order.
+///
+/// Rotation and scale happen about an optional `pivot` point (the origin when absent), matching the pivot
+/// semantics of most DCC tools: points are moved by `-pivot`, scaled, rotated, moved back by `pivot`, and then translated.
+/// Every component is optional, and a missing component has no effect. This is embedded in both
+/// `TransPolyMeshPtr` and `PolyChildReference`, but can be used on its own.
+///
+/// Each component is stored as a flat array (ex: `"translation": [1.0, 0.0, 0.0]`), though the older object form is still read.
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Default)]
+pub struct PolyTransform {
+
+    /// Optional translation
+    #[serde(default, with = "crate::common::serialization::compact::optional_vector")]
+    pub translation: Option<PolyVector>,
+
+    /// Optional rotation
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "crate::common::serialization::compact::optional_quaternion")]
+    pub rotation: Option<PolyQuaternion>,
+
+    /// Optional component-wise scale
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "crate::common::serialization::compact::optional_vector")]
+    pub scale: Option<PolyVector>,
+
+    /// Optional point that rotation and scale are applied about
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "crate::common::serialization::compact::optional_vector")]
+    pub pivot: Option<PolyVector>
+
+}
+
+impl PolyTransform {
+
+    /// A transform that does nothing
+    pub const IDENTITY: PolyTransform = PolyTransform {
+        translation: None,
+        rotation: None,
+        scale: None,
+        pivot: None
+    };
+
+    /// A transform that does nothing
+    pub fn identity() -> Self {
+        Self::IDENTITY
+    }
+
+    /// Check if this transform leaves every point where it is, to within `epsilon` per component.
+    ///
+    /// A pivot has no effect without a rotation or scale, so it is ignored
+    pub fn is_identity(&self, epsilon: f32) -> bool {
+        let translation = self.get_translation();
+        let rotation = self.get_rotation().normalized();
+        let scale = self.get_scale();
+        let near = |value: f32, target: f32| (value - target).abs() <= epsilon;
+
+        // Both q and -q describe the same rotation
+        near(translation.x, 0.0) && near(translation.y, 0.0) && near(translation.z, 0.0)
+            && near(rotation.x, 0.0) && near(rotation.y, 0.0) && near(rotation.z, 0.0) && near(rotation.w.abs(), 1.0)
+            && near(scale.x, 1.0) && near(scale.y, 1.0) && near(scale.z, 1.0)
+    }
+
+    /// A transform that only translates
+    pub fn from_translation(translation: PolyVector) -> Self {
+        Self {
+            translation: Some(translation),
+            ..Self::default()
+        }
+    }
+
+    pub fn get_translation(&self) -> PolyVector {
+        self.translation.unwrap_or_else(PolyVector::zero)
+    }
+
+    pub fn get_rotation(&self) -> PolyQuaternion {
+        self.rotation.unwrap_or_else(PolyQuaternion::identity)
+    }
+
+    pub fn get_scale(&self) -> PolyVector {
+        self.scale.unwrap_or_else(PolyVector::unit)
+    }
+
+    /// Get the translation this transform would need if its pivot were at the origin
+    pub fn baked_translation(&self) -> PolyVector {
+        match self.pivot {
+            Some(pivot) => self.get_translation() + pivot - self.get_rotation().rotate_vector(pivot.component_mul(self.get_scale())),
+            None => self.get_translation()
+        }
+    }
+
+    /// Combine this transform with a `parent` transform, producing a transform that applies this one, then the parent.
+    ///
+    /// Any pivots are baked into the resulting translation. Non-uniform parent scale combined with rotation can not be
+    /// represented exactly as a single transform, in which case the scales are multiplied component-wise
+    pub fn compose(&self, parent: &PolyTransform) -> Self {
+
+        // Only set components that either side actually uses
+        let parent_rotation = parent.get_rotation();
+        let parent_scale = parent.get_scale();
+        let translation = parent.baked_translation() + parent_rotation.rotate_vector(self.baked_translation().component_mul(parent_scale));
+        let rotation = match (parent.rotation, self.rotation) {
+            (None, None) => None,
+            _ => Some(parent_rotation * self.get_rotation())
+        };
+        let scale = match (parent.scale, self.scale) {
+            (None, None) => None,
+            _ => Some(parent_scale.component_mul(self.get_scale()))
+        };
+
+        Self {
+            translation: Some(translation),
+            rotation,
+            scale,
+            pivot: None
+        }
+    }
+
+    /// Check if this transform collapses geometry onto a plane, line, or point (or worse), meaning it can not be undone.
+    ///
+    /// This is the case when any scale component is (nearly) zero, the rotation has no length, or any component is NaN or infinite
+    // `Option::is_none_or` needs a newer compiler than this crate asks for, so newer clippy's suggestion to use it is ignored
+    #[allow(unknown_lints, clippy::unnecessary_map_or)]
+    pub fn is_degenerate(&self) -> bool {
+        let scale = self.get_scale();
+        let rotation = self.get_rotation();
+        let rotation_length = (rotation.x * rotation.x + rotation.y * rotation.y + rotation.z * rotation.z + rotation.w * rotation.w).sqrt();
+        let finite = self.get_translation().is_finite() && scale.is_finite() && rotation_length.is_finite() && self.pivot.map_or(true, |pivot| pivot.is_finite());
+
+        !finite || rotation_length <= f32::EPSILON || scale.x.abs() <= f32::EPSILON || scale.y.abs() <= f32::EPSILON || scale.z.abs() <= f32::EPSILON
+    }
+
+    /// Get the transform that undoes this one, with any pivot baked in. Like `compose`, this is only exact for uniform scales.
+    ///
+    /// Fails with `PolyMeshError::DegenerateTransform` if this transform can not be undone (see `is_degenerate`)
+    pub fn inverse(&self) -> Result<Self, PolyMeshError> {
+        if self.is_degenerate() {
+            return Err(PolyMeshError::DegenerateTransform(format!("{:?}", self)));
+        }
+        let rotation = self.get_rotation().conjugate();
+        let scale = self.get_scale();
+        let inverse_scale = PolyVector { x: 1.0 / scale.x, y: 1.0 / scale.y, z: 1.0 / scale.z };
+        let translation = rotation.rotate_vector(self.baked_translation() * -1.0).component_mul(inverse_scale);
+
+        Ok(Self {
+            translation: Some(translation),
+            rotation: self.rotation.map(|_| rotation),
+            scale: self.scale.map(|_| inverse_scale),
+            pivot: None
+        })
+    }
+
+    /// Mirror this transform across the plane perpendicular to `axis`.
+    ///
+    /// The translation is reflected, and the rotation is reflected so it turns the same way in the mirrored space
+    pub fn mirrored(&self, axis: Axis) -> Self {
+        Self {
+            translation: self.translation.map(|translation| translation.mirrored(axis)),
+            rotation: self.rotation.map(|rotation| {
+                let axis_part = PolyVector { x: rotation.x, y: rotation.y, z: rotation.z }.mirrored(axis) * -1.0;
+                PolyQuaternion { x: axis_part.x, y: axis_part.y, z: axis_part.z, w: rotation.w }
+            }),
+            scale: self.scale,
+            pivot: self.pivot.map(|pivot| pivot.mirrored(axis))
+        }
+    }
+
+    /// Apply this transform to a single point
+    pub fn transform_point(&self, point: PolyVector) -> PolyVector {
+        self.get_rotation().rotate_vector(point.component_mul(self.get_scale())) + self.baked_translation()
+    }
+
+    /// Convert this transform to a matrix
+    pub fn to_matrix(&self) -> PolyMatrix4 {
+        PolyMatrix4::from_translation(self.baked_translation())
+            * PolyMatrix4::from_rotation(self.get_rotation())
+            * PolyMatrix4::from_scale(self.get_scale())
+    }
+
+}
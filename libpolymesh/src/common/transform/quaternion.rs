@@ -0,0 +1,108 @@
+use serde::{Deserialize, Serialize};
+use std::ops::Mul;
+use super::vector::PolyVector;
+
+/// Simple, JSON-Serializable rotation quaternion
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq)]
+pub struct PolyQuaternion {
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+    pub w: f32
+}
+
+impl PolyQuaternion {
+
+    pub fn identity() -> Self {
+        Self {
+            x: 0.0,
+            y: 0.0,
+            z: 0.0,
+            w: 1.0
+        }
+    }
+
+    /// Create a rotation of `angle` radians about `axis`
+    pub fn from_axis_angle(axis: PolyVector, angle: f32) -> Self {
+        let length = (axis.x * axis.x + axis.y * axis.y + axis.z * axis.z).sqrt();
+        let half_sin = (angle / 2.0).sin() / length;
+        Self {
+            x: axis.x * half_sin,
+            y: axis.y * half_sin,
+            z: axis.z * half_sin,
+            w: (angle / 2.0).cos()
+        }
+    }
+
+    /// Get the inverse rotation (assuming this is a unit quaternion)
+    pub fn conjugate(&self) -> Self {
+        Self {
+            x: -self.x,
+            y: -self.y,
+            z: -self.z,
+            w: self.w
+        }
+    }
+
+    /// Get this quaternion scaled to unit length
+    pub fn normalized(&self) -> Self {
+        let length = (self.x * self.x + self.y * self.y + self.z * self.z + self.w * self.w).sqrt();
+        Self {
+            x: self.x / length,
+            y: self.y / length,
+            z: self.z / length,
+            w: self.w / length
+        }
+    }
+
+    /// Rotate a vector by this quaternion
+    pub fn rotate_vector(&self, vector: PolyVector) -> PolyVector {
+        let rotated = *self * PolyQuaternion { x: vector.x, y: vector.y, z: vector.z, w: 0.0 } * self.conjugate();
+        PolyVector {
+            x: rotated.x,
+            y: rotated.y,
+            z: rotated.z
+        }
+    }
+
+    /// Spherically interpolate from `a` to `b` by `t`
+    pub fn slerp(a: PolyQuaternion, b: PolyQuaternion, t: f32) -> PolyQuaternion {
+
+        // Take the shortest path between the rotations
+        let mut dot = a.x * b.x + a.y * b.y + a.z * b.z + a.w * b.w;
+        let mut b = b;
+        if dot < 0.0 {
+            b = PolyQuaternion { x: -b.x, y: -b.y, z: -b.z, w: -b.w };
+            dot = -dot;
+        }
+
+        // Fall back to a normalized lerp for nearly identical rotations
+        let (weight_a, weight_b) = if dot > 0.9995 {
+            (1.0 - t, t)
+        } else {
+            let theta = dot.acos();
+            (((1.0 - t) * theta).sin() / theta.sin(), (t * theta).sin() / theta.sin())
+        };
+
+        PolyQuaternion {
+            x: a.x * weight_a + b.x * weight_b,
+            y: a.y * weight_a + b.y * weight_b,
+            z: a.z * weight_a + b.z * weight_b,
+            w: a.w * weight_a + b.w * weight_b
+        }.normalized()
+    }
+
+}
+
+impl Mul for PolyQuaternion {
+    type Output = PolyQuaternion;
+
+    fn mul(self, other: PolyQuaternion) -> PolyQuaternion {
+        PolyQuaternion {
+            x: self.w * other.x + self.x * other.w + self.y * other.z - self.z * other.y,
+            y: self.w * other.y - self.x * other.z + self.y * other.w + self.z * other.x,
+            z: self.w * other.z + self.x * other.y - self.y * other.x + self.z * other.w,
+            w: self.w * other.w - self.x * other.x - self.y * other.y - self.z * other.z
+        }
+    }
+}
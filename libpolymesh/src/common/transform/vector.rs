@@ -3,6 +3,14 @@ extern crate derive_more;
 use serde::{Deserialize, Serialize};
 use derive_more::{Add, Sub, Mul, Div};
 
+/// One of the three coordinate axes
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Axis {
+    X,
+    Y,
+    Z
+}
+
 /// Simple, JSON-Serializable vector
 #[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq, PartialOrd, Add, Sub, Mul, Div)]
 pub struct PolyVector {
@@ -53,6 +61,98 @@ impl PolyVector {
         }
     }
 
+    /// Check that no component of this vector is NaN or infinite
+    ///
+    /// ```rust
+    /// use libpolymesh::prelude as pmf;
+    ///
+    /// assert!(pmf::PolyVector::unit().is_finite());
+    /// assert!(!pmf::PolyVector { x: f32::NAN, y: 0.0, z: 0.0 }.is_finite());
+    /// assert!(!pmf::PolyVector { x: 0.0, y: f32::INFINITY, z: 0.0 }.is_finite());
+    /// assert!(!pmf::PolyVector { x: 0.0, y: 0.0, z: f32::NEG_INFINITY }.is_finite());
+    /// ```
+    pub fn is_finite(&self) -> bool {
+        self.x.is_finite() && self.y.is_finite() && self.z.is_finite()
+    }
+
+    /// Snap every component to the nearest multiple of `grid`. Panics if `grid` is not a positive finite number
+    pub fn quantized(&self, grid: f32) -> Self {
+        assert!(grid.is_finite() && grid > 0.0, "Quantization grid must be positive, got {}", grid);
+        Self {
+            x: (self.x / grid).round() * grid,
+            y: (self.y / grid).round() * grid,
+            z: (self.z / grid).round() * grid
+        }
+    }
+
+    /// Get a copy of this vector with the component along `axis` negated
+    pub fn mirrored(&self, axis: Axis) -> PolyVector {
+        let mut output = *self;
+        match axis {
+            Axis::X => output.x = -output.x,
+            Axis::Y => output.y = -output.y,
+            Axis::Z => output.z = -output.z
+        }
+        output
+    }
+
+    /// Multiply each component by the matching component of `other`
+    pub fn component_mul(&self, other: PolyVector) -> PolyVector {
+        PolyVector {
+            x: self.x * other.x,
+            y: self.y * other.y,
+            z: self.z * other.z
+        }
+    }
+
+    /// Linearly interpolate from `a` to `b` by `t`
+    pub fn lerp(a: PolyVector, b: PolyVector, t: f32) -> PolyVector {
+        a + (b - a) * t
+    }
+
+    /// Get the smallest of each component of `a` and `b`
+    pub fn component_min(a: PolyVector, b: PolyVector) -> PolyVector {
+        PolyVector {
+            x: a.x.min(b.x),
+            y: a.y.min(b.y),
+            z: a.z.min(b.z)
+        }
+    }
+
+    /// Get the largest of each component of `a` and `b`
+    pub fn component_max(a: PolyVector, b: PolyVector) -> PolyVector {
+        PolyVector {
+            x: a.x.max(b.x),
+            y: a.y.max(b.y),
+            z: a.z.max(b.z)
+        }
+    }
+
+    /// Get the dot product of this vector and `other`
+    pub fn dot(&self, other: PolyVector) -> f32 {
+        self.x * other.x + self.y * other.y + self.z * other.z
+    }
+
+    /// Get the cross product of this vector and `other`
+    pub fn cross(&self, other: PolyVector) -> PolyVector {
+        PolyVector {
+            x: self.y * other.z - self.z * other.y,
+            y: self.z * other.x - self.x * other.z,
+            z: self.x * other.y - self.y * other.x
+        }
+    }
+
+    /// Get the angle between this vector and `other` in radians. Returns 0 if either vector has no length
+    pub fn angle_between(&self, other: PolyVector) -> f32 {
+        let lengths = (self.dot(*self) * other.dot(other)).sqrt();
+        if lengths == 0.0 {
+            return 0.0;
+        }
+
+        // Clamp to keep rounding error from pushing acos out of its domain
+        (self.dot(other) / lengths).clamp(-1.0, 1.0).acos()
+    }
+
     pub fn max(a: PolyVector, b: PolyVector) -> PolyVector {
         if a>b {a} else {b}
     }
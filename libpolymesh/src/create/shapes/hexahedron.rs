@@ -4,7 +4,8 @@ use crate::common::{
     TransPolyMeshPtr,
     transform::{
         PolyVector,
-        PolyColor
+        PolyColor,
+        PolyTransform
     }
 };
 use super::quad::make_quad;
@@ -43,7 +44,7 @@ pub fn make_hexahedron(corner_a: PolyVector, corner_b: PolyVector, color: PolyCo
             x_max_corner - positive_x_transform,
             color
         )),
-        translation: Some(positive_x_transform)
+        transform: PolyTransform::from_translation(positive_x_transform)
     });
     parent.add_child(TransPolyMeshPtr {
         path: "/faces_gr/positive_y_geo".to_string(),
@@ -54,7 +55,7 @@ pub fn make_hexahedron(corner_a: PolyVector, corner_b: PolyVector, color: PolyCo
             all_max_corner - positive_y_transform,
             color
         )),
-        translation: Some(positive_y_transform)
+        transform: PolyTransform::from_translation(positive_y_transform)
     });
     parent.add_child(TransPolyMeshPtr {
         path: "/faces_gr/positive_z_geo".to_string(),
@@ -65,7 +66,7 @@ pub fn make_hexahedron(corner_a: PolyVector, corner_b: PolyVector, color: PolyCo
             xz_max_corner - positive_z_transform,
             color
         )),
-        translation: Some(positive_z_transform)
+        transform: PolyTransform::from_translation(positive_z_transform)
     });
     parent.add_child(TransPolyMeshPtr {
         path: "/faces_gr/negative_x_geo".to_string(),
@@ -76,7 +77,7 @@ pub fn make_hexahedron(corner_a: PolyVector, corner_b: PolyVector, color: PolyCo
             z_max_corner - negative_x_transform,
             color
         )),
-        translation: Some(negative_x_transform)
+        transform: PolyTransform::from_translation(negative_x_transform)
     });
     parent.add_child(TransPolyMeshPtr {
         path: "/faces_gr/negative_y_geo".to_string(),
@@ -87,7 +88,7 @@ pub fn make_hexahedron(corner_a: PolyVector, corner_b: PolyVector, color: PolyCo
             x_max_corner - negative_y_transform,
             color
         )),
-        translation: Some(negative_y_transform)
+        transform: PolyTransform::from_translation(negative_y_transform)
     });
     parent.add_child(TransPolyMeshPtr {
         path: "/faces_gr/negative_z_geo".to_string(),
@@ -98,7 +99,7 @@ pub fn make_hexahedron(corner_a: PolyVector, corner_b: PolyVector, color: PolyCo
             all_min_corner - negative_z_transform,
             color
         )),
-        translation: Some(negative_z_transform)
+        transform: PolyTransform::from_translation(negative_z_transform)
     });
 
     return parent;
@@ -31,7 +31,12 @@ pub fn make_quad(top_left: PolyVector, top_right: PolyVector, bottom_left: PolyV
     // Create the geo
     let geometry = Some(MeshDef {
         color: color,
-        triangles: Some(triangles)
+        triangles: Some(triangles),
+        double_sided: false,
+        material: None,
+        joints: None,
+        weights: None,
+        uvs: None
     });
 
     // Create the PolyMesh
@@ -0,0 +1,6 @@
+//! Utilities for exporting PolyMesh data to other file formats
+
+pub mod obj;
+mod precision;
+
+pub use precision::ExportPrecision;
@@ -0,0 +1,128 @@
+use crate::{
+    common::{
+        PolyMesh,
+        MeshDef,
+        PolyMeshError
+    },
+    util::flatlist::flatten_tree
+};
+use super::ExportPrecision;
+use std::collections::{
+    HashMap,
+    HashSet
+};
+use std::fs::{
+    self,
+    File
+};
+use std::io::{
+    BufWriter,
+    Write
+};
+use std::path::{
+    Path,
+    PathBuf
+};
+
+/// Write a single mesh as a Wavefront OBJ file. Every triangle gets its own three vertices
+pub fn write_obj<W: Write>(mesh: &MeshDef, writer: &mut W) -> Result<(), PolyMeshError> {
+    write_obj_impl(mesh, writer, None)
+}
+
+/// Write a single mesh as a Wavefront OBJ file, rounding every coordinate to `precision`. See `write_obj`
+pub fn write_obj_with_precision<W: Write>(mesh: &MeshDef, writer: &mut W, precision: ExportPrecision) -> Result<(), PolyMeshError> {
+    write_obj_impl(mesh, writer, Some(precision))
+}
+
+fn write_obj_impl<W: Write>(mesh: &MeshDef, writer: &mut W, precision: Option<ExportPrecision>) -> Result<(), PolyMeshError> {
+    let triangles = match &mesh.triangles {
+        Some(triangles) => triangles,
+        None => return Ok(())
+    };
+
+    // Write the vertices
+    for triangle in triangles {
+        for point in triangle {
+            match precision {
+                Some(precision) => writeln!(writer, "v {} {} {}", precision.format(point.x), precision.format(point.y), precision.format(point.z))?,
+                None => writeln!(writer, "v {} {} {}", point.x, point.y, point.z)?
+            }
+        }
+    }
+
+    // Write the faces (OBJ indices start at 1)
+    for i in 0..triangles.len() {
+        writeln!(writer, "f {} {} {}", i * 3 + 1, i * 3 + 2, i * 3 + 3)?;
+    }
+
+    Ok(())
+}
+
+/// Write every geometry-bearing node under `root` to its own `<node_name>.obj` file in `out_dir`, using world-space vertices.
+///
+/// `out_dir` is created if needed. Unnamed nodes are named after their tree path (ex: `faces_gr_positive_x_geo.obj`),
+/// and nodes that share a name get the lowest numeric suffix no other file is using (ex: `Quad Face_1.obj`).
+/// Returns the paths of all files written
+///
+/// ```rust
+/// use libpolymesh::prelude as pmf;
+/// use libpolymesh::common::{PolyMesh, MeshType, TransPolyMeshPtr};
+/// use libpolymesh::export::obj::write_obj_split;
+/// use tempdir::TempDir;
+///
+/// // Two parts named "Part", and one that is already called "Part_1"
+/// let mut root = PolyMesh::new(MeshType::Group, None);
+/// for (path, name) in [("/a", "Part"), ("/b", "Part"), ("/c", "Part_1")] {
+///     let mut quad = pmf::make_quad(pmf::PolyVector::unit_y(), pmf::PolyVector::unit(), pmf::PolyVector::zero(), pmf::PolyVector::unit_x(), pmf::PolyColor::green());
+///     quad.set_name(name.to_string());
+///     root.add_child(TransPolyMeshPtr::new(path.to_string(), quad, None));
+/// }
+///
+/// let workspace = TempDir::new("obj_split").unwrap();
+/// let written = write_obj_split(&root, workspace.path()).unwrap();
+/// let names: Vec<_> = written.iter().map(|path| path.file_name().unwrap().to_string_lossy().to_string()).collect();
+/// assert_eq!(names, vec!["Part.obj", "Part_1.obj", "Part_1_1.obj"]);
+/// ```
+pub fn write_obj_split(root: &PolyMesh, out_dir: &Path) -> Result<Vec<PathBuf>, PolyMeshError> {
+    write_obj_split_with_precision(root, out_dir, None)
+}
+
+/// Write every geometry-bearing node under `root` to its own `.obj` file, optionally rounding every coordinate to `precision`. See `write_obj_split`
+pub fn write_obj_split_with_precision(root: &PolyMesh, out_dir: &Path, precision: Option<ExportPrecision>) -> Result<Vec<PathBuf>, PolyMeshError> {
+    fs::create_dir_all(out_dir)?;
+
+    // Collect all geometry along with the name of the node it came from
+    let mut named_geometry = Vec::new();
+    flatten_tree(root, &mut |path, node, _, _, geometry| {
+        let name = node.mesh.name_or(|_| match path.trim_matches('/') {
+            "" => "Unnamed".to_string(),
+            path => path.to_string()
+        });
+        named_geometry.push((name, geometry));
+    });
+
+    // Write each mesh to a uniquely named file, skipping over suffixed names that another node already has
+    let mut used_names: HashSet<String> = HashSet::new();
+    let mut next_suffix: HashMap<String, usize> = HashMap::new();
+    let mut written = Vec::new();
+    for (name, geometry) in named_geometry {
+        let name = name.replace(['/', '\\'], "_");
+        let mut unique_name = name.clone();
+        if used_names.contains(&unique_name) {
+            let suffix = next_suffix.entry(name.clone()).or_insert(1);
+            while used_names.contains(&format!("{}_{}", name, suffix)) {
+                *suffix += 1;
+            }
+            unique_name = format!("{}_{}", name, suffix);
+        }
+        used_names.insert(unique_name.clone());
+
+        let file_path = out_dir.join(format!("{}.obj", unique_name));
+        let mut writer = BufWriter::new(File::create(&file_path)?);
+        write_obj_impl(&geometry, &mut writer, precision)?;
+        writer.flush()?;
+        written.push(file_path);
+    }
+
+    Ok(written)
+}
@@ -0,0 +1,68 @@
+use crate::common::MeshDef;
+
+/// How many decimal places to keep when writing coordinates, trading accuracy for smaller files.
+///
+/// Rounding to `decimals` places moves each coordinate by at most half of `10^-decimals` (ex: 0.0005 for 3 decimals)
+///
+/// ```rust
+/// use libpolymesh::prelude as pmf;
+/// use libpolymesh::export::ExportPrecision;
+/// use libpolymesh::write::write_unpacked_polymesh_with_options;
+/// use tempdir::TempDir;
+///
+/// let cube = pmf::make_hexahedron(pmf::PolyVector { x: 1.0 / 3.0, y: 2.0 / 7.0, z: 0.123456 }, pmf::PolyVector::zero(), pmf::PolyColor::green());
+/// let workspace = TempDir::new("precision").unwrap();
+/// let root = workspace.path().to_str().unwrap();
+/// let options = pmf::WriteOptions { precision: Some(ExportPrecision { decimals: 3 }), ..Default::default() };
+/// write_unpacked_polymesh_with_options(&cube, root, &options).unwrap();
+///
+/// // Every vertex comes back within half of 10^-3, allowing for f32 rounding on top
+/// let loaded = pmf::FlatPolyMesh::new(root).unwrap();
+/// let expected = pmf::get_flat_geometry(cube);
+/// assert_eq!(loaded.flat_meshes.len(), expected.len());
+/// for (actual, expected) in loaded.flat_meshes.iter().zip(expected.iter()) {
+///     for (actual, expected) in actual.triangles.iter().flatten().flatten().zip(expected.triangles.iter().flatten().flatten()) {
+///         let offset = *actual - *expected;
+///         assert!(offset.x.abs().max(offset.y.abs()).max(offset.z.abs()) <= 0.5e-3 + 1e-6);
+///     }
+/// }
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ExportPrecision {
+
+    /// Number of digits to keep after the decimal point
+    pub decimals: usize
+
+}
+
+impl ExportPrecision {
+
+    /// Format `value` with at most `decimals` digits after the decimal point, dropping trailing zeros (ex: `1.5` rather than `1.500`)
+    pub fn format(&self, value: f32) -> String {
+        let formatted = format!("{:.*}", self.decimals, value);
+        let trimmed = if formatted.contains('.') { formatted.trim_end_matches('0').trim_end_matches('.') } else { &formatted };
+
+        // Rounding can leave a negative zero behind
+        match trimmed {
+            "-0" => "0".to_string(),
+            trimmed => trimmed.to_string()
+        }
+    }
+
+    /// Round `value` to `decimals` digits, to the closest `f32`. JSON writers print these back out with no extra digits
+    pub fn round(&self, value: f32) -> f32 {
+        self.format(value).parse().unwrap_or(value)
+    }
+
+    /// Get a copy of `mesh` with every vertex component rounded (see `round`)
+    pub fn round_mesh(&self, mesh: &MeshDef) -> MeshDef {
+        let mut output = mesh.clone();
+        for point in output.triangles.iter_mut().flatten().flatten() {
+            point.x = self.round(point.x);
+            point.y = self.round(point.y);
+            point.z = self.round(point.z);
+        }
+        output
+    }
+
+}
@@ -0,0 +1,44 @@
+use crate::common::{
+    MeshDef,
+    PolyMeshError,
+    transform::{
+        PolyVector,
+        PolyColor
+    }
+};
+
+/// Build a terrain mesh from a row-major grid of `width * height` heights.
+///
+/// Each sample becomes a vertex at `(x * cell, heights[i], z * cell)`, and each cell between four samples is split into two upward-facing triangles.
+/// The mesh is white, and fails with `PolyMeshError::InvalidDimensions` if the number of heights does not match the grid size
+pub fn from_grid(heights: &[f32], width: usize, height: usize, cell: f32) -> Result<MeshDef, PolyMeshError> {
+    if width.checked_mul(height) != Some(heights.len()) {
+        return Err(PolyMeshError::InvalidDimensions(format!("Expected {}x{} heights, found {}", width, height, heights.len())));
+    }
+
+    // Get the vertex at a grid position
+    let vertex = |x: usize, z: usize| PolyVector {
+        x: x as f32 * cell,
+        y: heights[z * width + x],
+        z: z as f32 * cell
+    };
+
+    // Split every cell into two triangles
+    let mut triangles = Vec::new();
+    for z in 0..height.saturating_sub(1) {
+        for x in 0..width.saturating_sub(1) {
+            triangles.push([vertex(x, z), vertex(x, z + 1), vertex(x + 1, z)]);
+            triangles.push([vertex(x + 1, z), vertex(x, z + 1), vertex(x + 1, z + 1)]);
+        }
+    }
+
+    Ok(MeshDef {
+        color: PolyColor::white(),
+        triangles: Some(triangles),
+        double_sided: false,
+        material: None,
+        joints: None,
+        weights: None,
+        uvs: None
+    })
+}
@@ -0,0 +1,3 @@
+//! Utilities for building PolyMesh data from other formats
+
+pub mod heightmap;
@@ -11,4 +11,12 @@ pub mod common;
 pub mod create;
 pub mod read;
 pub mod write;
-pub mod prelude;
\ No newline at end of file
+pub mod anim;
+pub mod export;
+pub mod import;
+pub mod stats;
+pub mod accel;
+pub mod prelude;
+
+#[cfg(feature = "testkit")]
+pub mod testkit;
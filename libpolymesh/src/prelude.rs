@@ -5,9 +5,15 @@ pub use crate::common::{
     MeshType,
     TransPolyMeshPtr,
     MeshDef,
+    PolyMeshError,
     transform::{
         PolyVector,
-        PolyColor
+        PolyColor,
+        PolyMatrix4,
+        PolyQuaternion,
+        PolyTransform,
+        Axis,
+        transform_points
     }
 };
 pub use crate::create::shapes::{
@@ -15,9 +21,18 @@ pub use crate::create::shapes::{
     quad::make_quad
 };
 pub use crate::util::{
-    flatlist::get_flat_geometry,
+    flatlist::{
+        get_flat_geometry,
+        get_flat_geometry_cb,
+        collect_locators,
+        FlatPolyMesh
+    },
     io::{
         read_pmf,
-        write_pmf
+        read_pmf_with_options,
+        write_pmf,
+        write_pmf_with_options
     }
-};
\ No newline at end of file
+};
+pub use crate::write::WriteOptions;
+pub use crate::read::LoadOptions;
\ No newline at end of file
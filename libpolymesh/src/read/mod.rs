@@ -1,5 +1,15 @@
 mod read;
 mod unpack;
+mod options;
+mod strict;
 
-pub use read::read_unpacked_polymesh;
-pub use unpack::unpack_pmf;
\ No newline at end of file
+pub use read::{
+    read_unpacked_polymesh,
+    read_unpacked_polymesh_with_options
+};
+pub(crate) use read::read_unpacked_subtree;
+pub use unpack::unpack_pmf;
+pub use options::{
+    LoadOptions,
+    PathResolver
+};
\ No newline at end of file
@@ -0,0 +1,63 @@
+use std::fmt;
+
+/// Callback that maps the stored path of a child to the path to load instead (see `LoadOptions::path_resolver`)
+pub type PathResolver = Box<dyn Fn(&str) -> String + Send + Sync>;
+
+/// Options controlling how a PolyMesh is read from disk
+///
+/// ```rust
+/// use libpolymesh::prelude as pmf;
+/// use libpolymesh::common::PolyMeshError;
+/// use libpolymesh::read::read_unpacked_polymesh_with_options;
+/// use tempdir::TempDir;
+///
+/// let workspace = TempDir::new("non_finite").unwrap();
+/// let root = workspace.path();
+/// let strict = pmf::LoadOptions { reject_non_finite: true, ..Default::default() };
+/// std::fs::write(root.join("polymeta.json"), r#"{"version": 1.2, "type": "Geometry", "metadata": {}, "children": []}"#).unwrap();
+///
+/// // Coordinates too large for an f32 load as +Inf and -Inf
+/// for corner in [r#"{"x": 1e39, "y": 0, "z": 0}"#, r#"{"x": 0, "y": -1e39, "z": 0}"#] {
+///     let mesh = format!(r#"{{"color": {{"r": 0, "g": 255, "b": 0, "a": 255}}, "triangles": [[{}, {{"x": 1, "y": 0, "z": 0}}, {{"x": 0, "y": 1, "z": 0}}]]}}"#, corner);
+///     std::fs::write(root.join("mesh.json"), mesh).unwrap();
+///     assert!(read_unpacked_polymesh_with_options(root.to_str().unwrap(), &pmf::LoadOptions::default()).is_ok());
+///     assert!(matches!(read_unpacked_polymesh_with_options(root.to_str().unwrap(), &strict), Err(PolyMeshError::NonFiniteGeometry(_))));
+/// }
+///
+/// // JSON has no way to spell NaN, so a NaN vertex is written as null, and never loads at all
+/// let mut quad = pmf::make_quad(pmf::PolyVector::unit_y(), pmf::PolyVector::unit(), pmf::PolyVector::zero(), pmf::PolyVector::unit_x(), pmf::PolyColor::green());
+/// quad.geometry.as_mut().unwrap().triangles.as_mut().unwrap()[0][0].x = f32::NAN;
+/// let nan_root = TempDir::new("nan").unwrap();
+/// libpolymesh::write::write_unpacked_polymesh(&quad, nan_root.path().to_str().unwrap()).unwrap();
+/// assert!(read_unpacked_polymesh_with_options(nan_root.path().to_str().unwrap(), &pmf::LoadOptions::default()).is_err());
+/// ```
+#[derive(Default)]
+pub struct LoadOptions {
+
+    /// Fail with `PolyMeshError::NonFiniteGeometry` if any vertex contains a NaN or infinite component
+    pub reject_non_finite: bool,
+
+    /// Fail with `PolyMeshError::UnknownField` if a `polymeta.json` or `mesh.json` file contains a field that would otherwise be ignored.
+    /// Keys inside `metadata`, `blobs`, and `extras` are free-form, and never count as unknown
+    pub deny_unknown_fields: bool,
+
+    /// Fail with `PolyMeshError::DuplicateChild` if a `polymeta.json` lists the same child twice with the same transform.
+    /// Otherwise, a warning is logged and the duplicates are loaded as written
+    pub deny_duplicate_children: bool,
+
+    /// Called with the stored `path` of every child before it is resolved, returning the path to load instead (ex: to follow assets
+    /// that have moved). The returned path is also used for the child in the loaded hierarchy. With no resolver, paths are used as written
+    pub path_resolver: Option<PathResolver>
+
+}
+
+impl fmt::Debug for LoadOptions {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("LoadOptions")
+            .field("reject_non_finite", &self.reject_non_finite)
+            .field("deny_unknown_fields", &self.deny_unknown_fields)
+            .field("deny_duplicate_children", &self.deny_duplicate_children)
+            .field("path_resolver", &self.path_resolver.as_ref().map(|_| "PathResolver"))
+            .finish()
+    }
+}
@@ -1,88 +1,247 @@
 use super::super::{
     common::{
-        serialization::data::{
-            polymeta::PolyMeta,
-            mesh::MeshDef
+        serialization::{
+            data::{
+                polymeta::PolyMeta,
+                mesh::MeshDef
+            },
+            text::read_json_file
         },
         mesh::{
             PolyMesh,
             MeshType,
-            TransPolyMeshPtr
-        }
+            TransPolyMeshPtr,
+            DeferredSource
+        },
+        PolyMeshError
     },
     util::{
         make_polymeta_file_path,
         make_mesh_file_path,
-        make_child_file_path
+        make_reference_file_path
     }
 };
-use serde_json::Result;
-use std::collections::HashMap;
+use super::{
+    LoadOptions,
+    strict
+};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use std::collections::{
+    HashMap,
+    HashSet
+};
+use std::fs;
+use std::path::{
+    Path,
+    PathBuf
+};
+
+/// Read a PolyMesh from a root directory in "unpacked" mode.
+///
+/// A leading UTF-8 byte order mark (as written by some Windows tools) is skipped in every file, and a file that is
+/// not UTF-8 at all fails with `PolyMeshError::Encoding`
+///
+/// ```rust
+/// use libpolymesh::read::read_unpacked_polymesh;
+/// use tempdir::TempDir;
+///
+/// let workspace = TempDir::new("bom").unwrap();
+/// let polymeta = b"\xEF\xBB\xBF{\"version\": 1.2, \"type\": \"Group\", \"metadata\": {}, \"children\": []}";
+/// std::fs::write(workspace.path().join("polymeta.json"), &polymeta[..]).unwrap();
+/// assert!(read_unpacked_polymesh(workspace.path().to_str().unwrap()).is_ok());
+///
+/// std::fs::write(workspace.path().join("polymeta.json"), b"\xFF\xFE{}").unwrap();
+/// assert!(matches!(read_unpacked_polymesh(workspace.path().to_str().unwrap()), Err(libpolymesh::common::PolyMeshError::Encoding(_))));
+/// ```
+///
+/// A child that points back at a directory that is still being read (ex: the scene root itself) fails with
+/// `PolyMeshError::UnresolvedPath` instead of recursing forever
+///
+/// ```rust
+/// use libpolymesh::read::read_unpacked_polymesh;
+/// use tempdir::TempDir;
+///
+/// let workspace = TempDir::new("cycle").unwrap();
+/// let polymeta = r#"{"version": 1.2, "type": "Group", "metadata": {}, "children": [{"path": "/", "translation": [0, 0, 0], "path_mode": "RelativeToRoot"}]}"#;
+/// std::fs::write(workspace.path().join("polymeta.json"), polymeta).unwrap();
+/// assert!(matches!(read_unpacked_polymesh(workspace.path().to_str().unwrap()), Err(libpolymesh::common::PolyMeshError::UnresolvedPath(_))));
+/// ```
+pub fn read_unpacked_polymesh(root_path: &str) -> Result<PolyMesh, PolyMeshError> {
+    read_unpacked_polymesh_with_options(root_path, &LoadOptions::default())
+}
 
-pub fn read_unpacked_polymesh(root_path: &str) -> Result<PolyMesh> {
+/// Read a PolyMesh from a root directory in "unpacked" mode, using custom `LoadOptions`
+pub fn read_unpacked_polymesh_with_options(root_path: &str, options: &LoadOptions) -> Result<PolyMesh, PolyMeshError> {
 
     // Create a lookup table to be used for caching meshes
     let mut mesh_table = HashMap::new();
 
     // Read recursively
-    return read_unpacked_polymesh_recursive(root_path, &mut mesh_table)
+    read_unpacked_polymesh_recursive(Path::new(root_path), Path::new(root_path), options, &|_| true, &mut mesh_table, &mut HashSet::new())
+}
+
+/// Read the mesh in `mesh_path` as part of the scene at `scene_root`, so `PathMode::RelativeToRoot` children still resolve against the real root.
+/// Only nodes whose `PolyMeta` passes `filter` have their geometry loaded. Rejected nodes are kept in the tree without geometry
+pub(crate) fn read_unpacked_subtree(mesh_path: &Path, scene_root: &Path, options: &LoadOptions, filter: &dyn Fn(&PolyMeta) -> bool) -> Result<PolyMesh, PolyMeshError> {
+    let mut mesh_table = HashMap::new();
+    read_unpacked_polymesh_recursive(mesh_path, scene_root, options, filter, &mut mesh_table, &mut HashSet::new())
 }
 
-fn read_unpacked_polymesh_recursive(root_path: &str, resolved_lookup: &mut HashMap<String, Box<PolyMesh>>) -> Result<PolyMesh> {
+fn read_unpacked_polymesh_recursive(root_path: &Path, scene_root: &Path, options: &LoadOptions, filter: &dyn Fn(&PolyMeta) -> bool, resolved_lookup: &mut HashMap<PathBuf, Box<PolyMesh>>, in_progress: &mut HashSet<PathBuf>) -> Result<PolyMesh, PolyMeshError> {
+
+    // A child that points back into a directory still being read would recurse forever
+    let directory = fs::canonicalize(root_path).unwrap_or_else(|_| root_path.to_path_buf());
+    if !in_progress.insert(directory.clone()) {
+        return Err(PolyMeshError::UnresolvedPath(format!("{} refers back to itself through its children", root_path.display())));
+    }
 
     // Read the polymeta
     let polymeta_path = make_polymeta_file_path(root_path);
-    let polymeta = PolyMeta::from_file(&polymeta_path).expect(&format!("Could not read polymeta.json from: {}", polymeta_path));
+    let polymeta = read_polymeta(&polymeta_path, options)?;
 
-    // If needed, read geometry from mesh.json
-    let mut geometry: Option<MeshDef> = None;
-    if polymeta.mesh_type == MeshType::Geometry || polymeta.mesh_type == MeshType::GeoGroup {
+    // Catch children that are listed twice in the same spot
+    for child in polymeta.duplicate_children() {
+        if options.deny_duplicate_children {
+            return Err(PolyMeshError::DuplicateChild(polymeta_path.display().to_string(), child));
+        }
+        log::warn!("{} lists {} more than once with the same transform", polymeta_path.display(), child);
+    }
+
+    // If needed, read geometry, preferring anything stored inline in the polymeta
+    let mut geometry: Option<MeshDef> = polymeta.geometry.clone();
+    let mut geometry_path = polymeta_path.clone();
+    let mesh_path = make_mesh_file_path(root_path);
+    if !filter(&polymeta) {
+
+        // Nodes rejected by the filter never have their geometry loaded
+        geometry = None;
+
+    } else if geometry.is_some() {
+
+        // Inline geometry always wins over a mesh.json
+        if mesh_path.exists() {
+            log::warn!("{} contains inline geometry, ignoring {}", polymeta_path.display(), mesh_path.display());
+        }
+
+    } else if polymeta.mesh_type == MeshType::Geometry || polymeta.mesh_type == MeshType::GeoGroup {
 
         // Read the mesh
-        let mesh_path = make_mesh_file_path(root_path);
-        let mesh_def = MeshDef::from_file(&mesh_path).expect(&format!("Could not read mesh.json from: {}", mesh_path));
+        let mesh_def: MeshDef = if options.deny_unknown_fields {
+            read_strict(&mesh_path, strict::check_mesh)?
+        } else {
+            read_json_file(&mesh_path)?
+        };
         geometry = Some(mesh_def);
+        geometry_path = mesh_path;
 
     }
 
-    // Create the base output mesh
-    let mut output = PolyMesh::new(polymeta.mesh_type, geometry);
+    // Optionally reject corrupt vertex data
+    if options.reject_non_finite && geometry.as_ref().is_some_and(|geometry| !geometry.is_finite()) {
+        return Err(PolyMeshError::NonFiniteGeometry(geometry_path.display().to_string()));
+    }
 
-    // Copy needed data
-    output.metadata = polymeta.metadata.clone();
+    // Create the base output mesh
+    let mut output = mesh_from_polymeta(&polymeta, geometry);
 
     // Add all children
     for child in &polymeta.children {
 
+        // Let the caller redirect the child before it is resolved
+        let mut child = child.clone();
+        if let Some(resolver) = &options.path_resolver {
+            child.path = resolver(&child.path);
+        }
+
         // Get the path to the child
-        let child_path = make_child_file_path(root_path, &child.path);
+        let child_path = make_reference_file_path(root_path, scene_root, &child);
 
-        // If this child has not already been resolved, resolve it
-        if !resolved_lookup.contains_key(&child_path) {
+        let child_mesh = if child.deferred {
 
-            // Read the mesh
-            let read_mesh = read_unpacked_polymesh_recursive(&child_path, resolved_lookup).unwrap();
+            // Deferred children are left as placeholders until they are asked for, so only their polymeta is read
+            let child_polymeta = read_polymeta(&make_polymeta_file_path(&child_path), options)?;
+            let mut placeholder = mesh_from_polymeta(&child_polymeta, None);
+            placeholder.deferred = true;
+            placeholder.deferred_source = Some(DeferredSource {
+                path: child_path,
+                bounds: child_polymeta.bounds()
+            });
+            Box::new(placeholder)
 
-            // Add to the table
-            resolved_lookup.insert(child_path.to_string(), Box::new(read_mesh));
+        } else {
 
-        }
+            // If this child has not already been resolved, resolve it
+            if !resolved_lookup.contains_key(&child_path) {
+
+                // Read the mesh
+                let read_mesh = read_unpacked_polymesh_recursive(&child_path, scene_root, options, filter, resolved_lookup, in_progress)?;
 
-        // Get a box of the child mesh
-        let child_mesh = resolved_lookup[&child_path].clone();
+                // Add to the table
+                resolved_lookup.insert(child_path.clone(), Box::new(read_mesh));
+
+            }
+
+            // Get a box of the child mesh
+            resolved_lookup[&child_path].clone()
+        };
 
         // Build a reference to the child
         let child_ref = TransPolyMeshPtr {
             path: (*child.path).to_string(),
             mesh: child_mesh,
-            translation: child.translation
+            transform: child.transform
         };
 
+        // A collapsed child is almost always an authoring mistake, and would otherwise silently vanish
+        if child_ref.is_degenerate() {
+            log::warn!("{} collapses {} with a degenerate transform", polymeta_path.display(), child.path);
+        }
+
         // Add the child to the mesh
         output.add_child(child_ref);
 
     }
 
+    in_progress.remove(&directory);
     Ok(output)
 
-}
\ No newline at end of file
+}
+
+/// Read a `polymeta.json` file, checking it for unknown fields if `options` asks for it
+fn read_polymeta(polymeta_path: &Path, options: &LoadOptions) -> Result<PolyMeta, PolyMeshError> {
+    let polymeta: PolyMeta = if options.deny_unknown_fields {
+        read_strict(polymeta_path, strict::check_polymeta)?
+    } else {
+        read_json_file(polymeta_path)?
+    };
+
+    // A unit that is not a positive size would scale every converted vertex into nonsense
+    if let Some(unit_scale) = polymeta.unit_scale.filter(|unit_scale| !unit_scale.is_finite() || *unit_scale <= 0.0) {
+        return Err(PolyMeshError::InvalidDimensions(format!("Unit scale must be positive, got {} in {}", unit_scale, polymeta_path.display())));
+    }
+    Ok(polymeta)
+}
+
+/// Create a mesh from everything stored in `polymeta` itself, without any of its children
+fn mesh_from_polymeta(polymeta: &PolyMeta, geometry: Option<MeshDef>) -> PolyMesh {
+    let mut output = PolyMesh::new(polymeta.mesh_type, geometry);
+    output.uuid = polymeta.uuid.clone();
+    output.notes = polymeta.notes.clone();
+    output.visible = polymeta.visible;
+    output.unit_scale = polymeta.unit_scale;
+    output.extras = polymeta.extras.clone();
+    output.metadata = polymeta.metadata.clone();
+    output.blobs = polymeta.blobs.clone();
+    output
+}
+
+/// Read a JSON file, failing with `PolyMeshError::UnknownField` if `check` finds a field that is not part of the format
+fn read_strict<T: DeserializeOwned>(file_path: &Path, check: fn(&Value) -> Result<(), String>) -> Result<T, PolyMeshError> {
+
+    // Parse the file loosely first, so its fields can be checked
+    let value: Value = read_json_file(file_path)?;
+    check(&value).map_err(|field| PolyMeshError::UnknownField(file_path.display().to_string(), field))?;
+
+    serde_json::from_value(value).map_err(|error| PolyMeshError::InvalidFile(file_path.display().to_string(), error))
+}
@@ -0,0 +1,71 @@
+//! Checks for fields that serde would otherwise silently ignore
+
+use serde_json::Value;
+
+const POLYMETA_FIELDS: &[&str] = &["version", "uuid", "type", "metadata", "blobs", "extras", "children", "notes", "visible", "unit_scale", "bounds", "mesh"];
+const CHILD_FIELDS: &[&str] = &["path", "translation", "rotation", "scale", "pivot", "path_mode", "deferred"];
+const MESH_FIELDS: &[&str] = &["color", "triangles", "double_sided", "material", "joints", "weights", "uvs"];
+const COLOR_FIELDS: &[&str] = &["r", "g", "b", "a"];
+const VECTOR_FIELDS: &[&str] = &["x", "y", "z"];
+const QUATERNION_FIELDS: &[&str] = &["x", "y", "z", "w"];
+
+/// Check that an object only contains `allowed` keys, returning the path of the first unknown one
+fn check_fields(value: &Value, allowed: &[&str], path: &str) -> Result<(), String> {
+    if let Value::Object(map) = value {
+        if let Some(key) = map.keys().find(|key| !allowed.contains(&key.as_str())) {
+            return Err(format!("{}{}", path, key));
+        }
+    }
+    Ok(())
+}
+
+/// Check a parsed `polymeta.json` file for unknown fields
+pub(crate) fn check_polymeta(value: &Value) -> Result<(), String> {
+    check_fields(value, POLYMETA_FIELDS, "")?;
+
+    // Check every child reference
+    if let Some(Value::Array(children)) = value.get("children") {
+        for (i, child) in children.iter().enumerate() {
+            let path = format!("children[{}].", i);
+            check_fields(child, CHILD_FIELDS, &path)?;
+            for key in &["translation", "scale", "pivot"] {
+                if let Some(vector) = child.get(key) {
+                    check_fields(vector, VECTOR_FIELDS, &format!("{}{}.", path, key))?;
+                }
+            }
+            if let Some(rotation) = child.get("rotation") {
+                check_fields(rotation, QUATERNION_FIELDS, &format!("{}rotation.", path))?;
+            }
+        }
+    }
+
+    // Check any inline geometry
+    match value.get("mesh") {
+        Some(mesh) => check_mesh_at(mesh, "mesh."),
+        None => Ok(())
+    }
+}
+
+/// Check a parsed `mesh.json` file for unknown fields
+pub(crate) fn check_mesh(value: &Value) -> Result<(), String> {
+    check_mesh_at(value, "")
+}
+
+fn check_mesh_at(value: &Value, path: &str) -> Result<(), String> {
+    check_fields(value, MESH_FIELDS, path)?;
+    if let Some(color) = value.get("color") {
+        check_fields(color, COLOR_FIELDS, &format!("{}color.", path))?;
+    }
+
+    // Check every vertex
+    if let Some(Value::Array(triangles)) = value.get("triangles") {
+        for (i, triangle) in triangles.iter().enumerate() {
+            if let Value::Array(points) = triangle {
+                for (j, point) in points.iter().enumerate() {
+                    check_fields(point, VECTOR_FIELDS, &format!("{}triangles[{}][{}].", path, i, j))?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,163 @@
+//! Size statistics for scenes on disk, computed without loading their geometry
+
+use crate::{
+    common::{
+        MeshType,
+        PolyMeshError,
+        serialization::{
+            data::polymeta::PolyMeta,
+            text::read_json_file
+        }
+    },
+    util::{
+        make_polymeta_file_path,
+        make_mesh_file_path,
+        make_reference_file_path
+    }
+};
+use derive_more::Add;
+use serde::{
+    Deserialize,
+    Deserializer,
+    de::{
+        IgnoredAny,
+        SeqAccess,
+        Visitor
+    }
+};
+use std::collections::{
+    HashMap,
+    HashSet
+};
+use std::fmt;
+use std::fs;
+use std::path::{
+    Path,
+    PathBuf
+};
+
+/// Totals describing the size of a scene
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Add)]
+pub struct MeshStats {
+
+    /// Number of meshes with geometry
+    pub meshes: usize,
+
+    /// Number of triangles
+    pub triangles: usize,
+
+    /// Number of vertices. This is always three per triangle (see `MeshDef::triangles`)
+    pub vertices: usize
+
+}
+
+impl MeshStats {
+
+    fn from_triangles(triangles: usize) -> Self {
+        Self {
+            meshes: 1,
+            triangles,
+            vertices: triangles * 3
+        }
+    }
+
+}
+
+/// A `mesh.json` file, with its triangles counted rather than stored
+#[derive(Deserialize)]
+struct CountedMeshDef {
+
+    #[serde(default, deserialize_with = "count_sequence")]
+    triangles: usize
+
+}
+
+/// Count the elements of an optional sequence while skipping over their contents
+fn count_sequence<'de, D: Deserializer<'de>>(deserializer: D) -> Result<usize, D::Error> {
+    struct CountVisitor;
+
+    impl<'de> Visitor<'de> for CountVisitor {
+        type Value = usize;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, "a list of triangles or null")
+        }
+
+        fn visit_unit<E>(self) -> Result<usize, E> {
+            Ok(0)
+        }
+
+        fn visit_none<E>(self) -> Result<usize, E> {
+            Ok(0)
+        }
+
+        fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<usize, D::Error> {
+            deserializer.deserialize_seq(CountVisitor)
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<usize, A::Error> {
+            let mut count = 0;
+            while seq.next_element::<IgnoredAny>()?.is_some() {
+                count += 1;
+            }
+            Ok(count)
+        }
+    }
+
+    deserializer.deserialize_option(CountVisitor)
+}
+
+/// Count the meshes, triangles, and vertices in the unpacked scene at `root_path`, without building any geometry.
+///
+/// Meshes referenced more than once are counted once per reference, but are only read from disk once. A child that points
+/// back at a directory that is still being counted fails with `PolyMeshError::UnresolvedPath`
+///
+/// ```rust
+/// use libpolymesh::stats::scene_stats;
+/// use tempdir::TempDir;
+///
+/// let workspace = TempDir::new("cycle").unwrap();
+/// let polymeta = r#"{"version": 1.2, "type": "Group", "metadata": {}, "children": [{"path": "/", "translation": [0, 0, 0], "path_mode": "RelativeToRoot"}]}"#;
+/// std::fs::write(workspace.path().join("polymeta.json"), polymeta).unwrap();
+/// assert!(matches!(scene_stats(workspace.path().to_str().unwrap()), Err(libpolymesh::common::PolyMeshError::UnresolvedPath(_))));
+/// ```
+pub fn scene_stats(root_path: &str) -> Result<MeshStats, PolyMeshError> {
+    let mut resolved_lookup = HashMap::new();
+    scene_stats_recursive(Path::new(root_path), Path::new(root_path), &mut resolved_lookup, &mut HashSet::new())
+}
+
+fn scene_stats_recursive(root_path: &Path, scene_root: &Path, resolved_lookup: &mut HashMap<PathBuf, MeshStats>, in_progress: &mut HashSet<PathBuf>) -> Result<MeshStats, PolyMeshError> {
+
+    // A child that points back into a directory still being counted would recurse forever
+    let directory = fs::canonicalize(root_path).unwrap_or_else(|_| root_path.to_path_buf());
+    if !in_progress.insert(directory.clone()) {
+        return Err(PolyMeshError::UnresolvedPath(format!("{} refers back to itself through its children", root_path.display())));
+    }
+
+    // Read the polymeta
+    let polymeta_path = make_polymeta_file_path(root_path);
+    let polymeta: PolyMeta = read_json_file(&polymeta_path)?;
+
+    // Count this mesh's own geometry, preferring anything stored inline in the polymeta
+    let mut stats = MeshStats::default();
+    if let Some(geometry) = &polymeta.geometry {
+        stats = MeshStats::from_triangles(geometry.triangles.as_ref().map_or(0, Vec::len));
+    } else if polymeta.mesh_type == MeshType::Geometry || polymeta.mesh_type == MeshType::GeoGroup {
+        let mesh_path = make_mesh_file_path(root_path);
+        let counted: CountedMeshDef = read_json_file(&mesh_path)?;
+        stats = MeshStats::from_triangles(counted.triangles);
+    }
+
+    // Add every child, once per reference
+    for child in &polymeta.children {
+        let child_path = make_reference_file_path(root_path, scene_root, child);
+        if !resolved_lookup.contains_key(&child_path) {
+            let child_stats = scene_stats_recursive(&child_path, scene_root, resolved_lookup, in_progress)?;
+            resolved_lookup.insert(child_path.clone(), child_stats);
+        }
+        stats = stats + resolved_lookup[&child_path];
+    }
+
+    in_progress.remove(&directory);
+    Ok(stats)
+}
@@ -0,0 +1,55 @@
+//! Helpers for testing code that produces or consumes PolyMesh data. Only built with the `testkit` feature
+//!
+//! ```rust
+//! use libpolymesh::prelude as pmf;
+//!
+//! let cube = pmf::make_hexahedron(pmf::PolyVector::unit(), pmf::PolyVector::zero(), pmf::PolyColor::green());
+//! libpolymesh::testkit::assert_roundtrip(&cube, 1e-6);
+//! ```
+
+use crate::{
+    common::PolyMesh,
+    util::flatlist::{
+        get_flat_geometry,
+        FlatPolyMesh
+    },
+    write::write_unpacked_polymesh
+};
+use tempdir::TempDir;
+
+/// Write `mesh` to a temporary directory, load it back through `FlatPolyMesh`, and panic if the flattened geometry
+/// does not match the original's to within `epsilon` per component.
+///
+/// Meshes are compared in flattening order, including their color, `double_sided` setting, material, skinning data, and texture coordinates
+pub fn assert_roundtrip(mesh: &PolyMesh, epsilon: f32) {
+    let workspace = TempDir::new("libpolymesh_testkit").expect("Failed to create a temporary directory");
+    let root_path = workspace.path().to_str().expect("Temporary directory path is not valid UTF-8");
+
+    // Round-trip the mesh
+    write_unpacked_polymesh(mesh, root_path).expect("Failed to write the mesh");
+    let loaded = FlatPolyMesh::new(root_path).expect("Failed to read the mesh back");
+    let expected = get_flat_geometry(mesh.clone());
+
+    assert_eq!(loaded.flat_meshes.len(), expected.len(), "Round-trip changed the number of meshes");
+    for (i, (actual, expected)) in loaded.flat_meshes.iter().zip(expected.iter()).enumerate() {
+        let path = &loaded.flat_paths[i];
+        assert_eq!(actual.color, expected.color, "Round-trip changed the color of {}", path);
+        assert_eq!(actual.double_sided, expected.double_sided, "Round-trip changed double_sided on {}", path);
+        assert_eq!(actual.material, expected.material, "Round-trip changed the material of {}", path);
+        assert_eq!(actual.joints, expected.joints, "Round-trip changed the skinning joints of {}", path);
+        assert_eq!(actual.weights, expected.weights, "Round-trip changed the skinning weights of {}", path);
+        assert_eq!(actual.uvs, expected.uvs, "Round-trip changed the texture coordinates of {}", path);
+
+        // Compare every vertex
+        let actual_triangles = actual.triangles.as_deref().unwrap_or_default();
+        let expected_triangles = expected.triangles.as_deref().unwrap_or_default();
+        assert_eq!(actual_triangles.len(), expected_triangles.len(), "Round-trip changed the number of triangles in {}", path);
+        for (actual_point, expected_point) in actual_triangles.iter().flatten().zip(expected_triangles.iter().flatten()) {
+            let offset = *actual_point - *expected_point;
+            assert!(
+                offset.x.abs() <= epsilon && offset.y.abs() <= epsilon && offset.z.abs() <= epsilon,
+                "Round-trip moved a vertex of {} from {:?} to {:?}", path, expected_point, actual_point
+            );
+        }
+    }
+}
@@ -1,48 +1,875 @@
-use crate::common::{
-    PolyMesh,
-    MeshDef,
-    TransPolyMeshPtr
+use crate::{
+    common::{
+        PolyMesh,
+        MeshType,
+        MeshDef,
+        TransPolyMeshPtr,
+        PolyMeshError,
+        transform::{
+            PolyVector,
+            PolyMatrix4,
+            PolyTransform
+        },
+        serialization::{
+            data::{
+                polymeta::PolyMeta,
+                mesh::bounds_of_points
+            },
+            text::read_json_file
+        }
+    },
+    read::{
+        read_unpacked_subtree,
+        LoadOptions
+    }
+};
+use super::{
+    normalize_tree_path,
+    make_polymeta_file_path,
+    make_mesh_file_path,
+    make_child_file_path,
+    make_reference_file_path
+};
+use std::cell::Cell;
+use std::fmt;
+use std::sync::Arc;
+use std::collections::{
+    HashMap,
+    HashSet
+};
+use std::fs;
+use std::io;
+use std::thread;
+use std::path::{
+    Path,
+    PathBuf
 };
 
 
+/// Get the world-space geometry of every visible mesh in the tree.
+///
+/// A node whose accumulated transform mirrors space (ex: a scale with one negative component) would otherwise come out
+/// inside-out, so its triangle winding is reversed to keep the faces pointing the same way
+///
+/// ```rust
+/// use libpolymesh::prelude as pmf;
+/// use libpolymesh::common::{PolyMesh, MeshType, TransPolyMeshPtr};
+///
+/// // A quad facing +Z, mirrored across X
+/// let quad = pmf::make_quad(pmf::PolyVector::unit_y(), pmf::PolyVector::unit(), pmf::PolyVector::zero(), pmf::PolyVector::unit_x(), pmf::PolyColor::green());
+/// let mut child = TransPolyMeshPtr::new("/quad".to_string(), quad, None);
+/// child.transform.scale = Some(pmf::PolyVector { x: -1.0, y: 1.0, z: 1.0 });
+/// let mut root = PolyMesh::new(MeshType::Group, None);
+/// root.add_child(child);
+///
+/// // Every triangle still faces +Z
+/// let flat = pmf::get_flat_geometry(root);
+/// for [a, b, c] in flat[0].triangles.as_ref().unwrap() {
+///     assert!((*b - *a).cross(*c - *a).z > 0.0);
+/// }
+/// ```
+///
+/// Transforms are multiplied out as matrices, so a rotated child under a non-uniform scale lands exactly where `PolyMesh::world_matrix` puts it:
+///
+/// ```rust
+/// use libpolymesh::prelude as pmf;
+/// use libpolymesh::common::{PolyMesh, MeshType, TransPolyMeshPtr, transform::PolyQuaternion};
+///
+/// // A child turned 90 degrees about Z, under a parent stretched along X
+/// let quad = pmf::make_quad(pmf::PolyVector::unit_y(), pmf::PolyVector::unit(), pmf::PolyVector::zero(), pmf::PolyVector::unit_x(), pmf::PolyColor::green());
+/// let mut child = TransPolyMeshPtr::new("/c".to_string(), quad, None);
+/// child.transform.rotation = Some(PolyQuaternion::from_axis_angle(pmf::PolyVector::unit_z(), std::f32::consts::FRAC_PI_2));
+/// let mut parent = TransPolyMeshPtr::new("/p".to_string(), PolyMesh::new(MeshType::Group, None), None);
+/// parent.transform.scale = Some(pmf::PolyVector { x: 2.0, y: 1.0, z: 1.0 });
+/// parent.mesh.add_child(child.clone());
+/// let mut root = PolyMesh::new(MeshType::Group, None);
+/// root.add_child(parent);
+///
+/// let world = root.world_matrix("/p/c").unwrap();
+/// let flat = pmf::get_flat_geometry(root);
+/// for (flat_triangle, local_triangle) in flat[0].triangles.as_ref().unwrap().iter().zip(child.mesh.geometry.as_ref().unwrap().triangles.as_ref().unwrap()) {
+///     for (flat_point, local_point) in flat_triangle.iter().zip(local_triangle.iter()) {
+///         let offset = *flat_point - world.transform_point(*local_point);
+///         assert!(offset.dot(offset).sqrt() < 1e-5);
+///     }
+/// }
+///
+/// // The child's local X axis now points along world Y, which the parent does not stretch
+/// let local_x = world.transform_point(pmf::PolyVector::unit_x()) - world.transform_point(pmf::PolyVector::zero());
+/// assert!((local_x.y - 1.0).abs() < 1e-5 && local_x.x.abs() < 1e-5);
+/// ```
 pub fn get_flat_geometry(root_mesh: PolyMesh) -> Vec<MeshDef> {
 
     // Create a list of all found geometry
     let mut all_geo = Vec::new();
 
     // Begin recursive search for geometry
-    get_flat_geometry_recursive(&root_mesh, None, &mut all_geo);
+    root_mesh.flatten_into(&mut all_geo);
 
     return all_geo;
 
 }
 
-fn get_flat_geometry_recursive(root_mesh: &PolyMesh, parent_transform: Option<&TransPolyMeshPtr>, all_geo: &mut Vec<MeshDef>){
+impl PolyMesh {
+
+    /// Flatten this mesh's world-space geometry into `out`, clearing it first.
+    ///
+    /// This reuses the allocation behind `out`, so calling it repeatedly with the same buffer avoids reallocating the list
+    pub fn flatten_into(&self, out: &mut Vec<MeshDef>) {
+        out.clear();
+        flatten_tree(self, &mut |_, _, _, _, geometry| out.push(geometry));
+    }
+
+    /// Flatten this mesh's world-space geometry like `get_flat_geometry`, pairing each piece with the tree path of the node it came from.
+    ///
+    /// Geometry stored on the root itself is paired with `/`
+    pub fn flatten_with_paths(&self) -> Vec<(String, MeshDef)> {
+        let mut all_geo = Vec::new();
+        flatten_tree(self, &mut |path, _, _, _, geometry| all_geo.push((path.to_string(), geometry)));
+        all_geo
+    }
+
+}
+
+/// Flatten `root_mesh`, also handing `callback` the absolute pointer (with its accumulated world transform) that produced each piece of geometry.
+///
+/// Geometry stored on the root itself comes with an untransformed pointer to a copy of the root that has no geometry or children.
+/// The geometry is always exact, but a rotation under a non-uniform scale skews space in a way no `PolyTransform` can hold, in which
+/// case the pointer's transform is the closest translation, rotation, and scale (see `PolyMatrix4::decompose`)
+pub fn get_flat_geometry_cb(root_mesh: &PolyMesh, callback: &mut impl FnMut(&MeshDef, &TransPolyMeshPtr)) {
+    flatten_tree(root_mesh, &mut |_, node, world, _, geometry| {
+        let transform = match world.decompose() {
+            Some((translation, rotation, scale)) => PolyTransform { translation: Some(translation), rotation: Some(rotation), scale: Some(scale), pivot: None },
+            None => PolyTransform { translation: Some(world.transform_point(PolyVector::zero())), scale: Some(PolyVector::zero()), ..PolyTransform::identity() }
+        };
+        callback(&geometry, &TransPolyMeshPtr { path: node.path.clone(), mesh: node.mesh.clone(), transform });
+    });
+}
+
+/// Get the tree path and world-space position of every locator under `root_mesh`, in depth-first order.
+///
+/// A locator is any node of type `MeshType::Locator`, or any other node with neither renderable geometry nor children (ex: an empty attachment point).
+/// These never show up in `get_flat_geometry`. Hidden nodes are skipped along with everything under them
+pub fn collect_locators(root_mesh: &PolyMesh) -> Vec<(String, PolyVector)> {
+    let mut locators = Vec::new();
+    if root_mesh.visible {
+        collect_locators_recursive(root_mesh, "", &PolyMatrix4::identity(), &mut locators);
+    }
+    locators
+}
+
+fn collect_locators_recursive(root_mesh: &PolyMesh, root_path: &str, parent_matrix: &PolyMatrix4, out: &mut Vec<(String, PolyVector)>) {
+    for child in root_mesh.children.iter().filter(|child| child.mesh.visible) {
+        let world = *parent_matrix * child.transform.to_matrix();
+        let child_path = format!("{}{}", root_path, child.path);
+
+        // The locator's position is wherever its local origin ends up
+        let child_mesh = child.mesh.as_ref();
+        if child_mesh.mesh_type == MeshType::Locator || (child_mesh.children.is_empty() && !child_mesh.has_renderable_geometry()) {
+            out.push((child_path.clone(), world.transform_point(PolyVector::zero())));
+        }
+
+        collect_locators_recursive(child_mesh, &child_path, &world, out);
+    }
+}
+
+/// Function handed the tree path, pointer, world matrix, effective runtime culling setting, and world-space geometry of a flattened node
+type FlatEmitter<'a> = dyn FnMut(&str, &TransPolyMeshPtr, &PolyMatrix4, bool, MeshDef) + 'a;
+
+/// Walk `root_mesh` and everything under it, emitting each node with renderable geometry (see `FlatEmitter`).
+///
+/// Transforms are accumulated as matrices, so a rotated child under a non-uniform scale comes out exactly as `PolyMesh::world_matrix` places it.
+/// The root sits at the origin, so a root with geometry of its own (ex: a single-part asset) is emitted untransformed at `/`.
+/// Hidden nodes are skipped along with everything under them
+pub(crate) fn flatten_tree(root_mesh: &PolyMesh, emit: &mut FlatEmitter) {
+    if !root_mesh.visible {
+        return;
+    }
+
+    let culling = root_mesh.resolve_runtime_culling(false);
+    if let Some(geometry) = root_mesh.geometry.as_ref().filter(|_| root_mesh.has_renderable_geometry()) {
+
+        // Point at the root without copying the rest of the tree
+        let mut root_copy = PolyMesh::new(root_mesh.mesh_type, None);
+        root_copy.uuid = root_mesh.uuid.clone();
+        root_copy.metadata = root_mesh.metadata.clone();
+        root_copy.blobs = root_mesh.blobs.clone();
+        root_copy.extras = root_mesh.extras.clone();
+        root_copy.notes = root_mesh.notes.clone();
+        let root_node = TransPolyMeshPtr::new("/".to_string(), root_copy, None);
+
+        emit("/", &root_node, &PolyMatrix4::identity(), culling, geometry.clone());
+    }
+    get_flat_geometry_recursive(root_mesh, "", &PolyMatrix4::identity(), culling, emit);
+}
+
+/// Walk every child of `root_mesh`, emitting each one with renderable geometry (see `FlatEmitter`).
+///
+/// `parent_matrix` places `root_mesh` in the world, and `inherited_culling` is the runtime culling setting that applies to its children
+fn get_flat_geometry_recursive(root_mesh: &PolyMesh, root_path: &str, parent_matrix: &PolyMatrix4, inherited_culling: bool, emit: &mut FlatEmitter){
 
 
-    // Search all children
-    for child in &root_mesh.children {
+    // Search all visible children
+    for child in root_mesh.children.iter().filter(|child| child.mesh.visible) {
 
-        // Get the child with an absolute transform from its root
-        let abs_child = child.new_from_transform_optional(parent_transform);
+        // Get the child's absolute transform from its root
+        let world = *parent_matrix * child.transform.to_matrix();
+        let child_path = format!("{}{}", root_path, child.path);
 
-        // Get the child's mesh
-        let child_mesh = abs_child.mesh.as_ref();
+        // Get the child's mesh, and the culling setting it ends up with
+        let child_mesh = child.mesh.as_ref();
+        let child_culling = child_mesh.resolve_runtime_culling(inherited_culling);
 
-        // Check if the child contains geometry
-        if child_mesh.contains_geometry() {
+        // Check if the child contains geometry (filtered loads may leave geometry nodes empty)
+        if let Some(geometry) = child_mesh.geometry.as_ref().filter(|_| child_mesh.has_renderable_geometry()) {
 
-            // Transform the child's geometry to an absolute position
-            let abs_geometry = child_mesh.geometry.as_ref().unwrap().transformed_by(&abs_child);
+            // Transform the child's geometry to an absolute position, skipping the work if it would not move
+            let abs_geometry = if world == PolyMatrix4::identity() { geometry.clone() } else { geometry.transformed_by_matrix(&world) };
 
             // Add the geometry to the list
-            all_geo.push(abs_geometry);
+            emit(&child_path, child, &world, child_culling, abs_geometry);
 
         }
 
         // Search for geometry
-        get_flat_geometry_recursive(child.mesh.as_ref(), Some(&abs_child), all_geo);
+        get_flat_geometry_recursive(child_mesh, &child_path, &world, child_culling, emit);
        
     }
 
-}
\ No newline at end of file
+}
+
+/// A PolyMesh loaded from an unpacked directory, along with its flattened, world-space geometry
+#[derive(Debug, Clone)]
+pub struct FlatPolyMesh {
+
+    /// Directory the mesh was loaded from
+    pub root_path: PathBuf,
+
+    /// The hierarchical mesh
+    pub root_mesh: PolyMesh,
+
+    /// World-space geometry of every mesh in the tree, converted to the target unit when loaded through `new_in_units`
+    pub flat_meshes: Vec<MeshDef>,
+
+    /// Tree path of the node that produced each entry in `flat_meshes`
+    pub flat_paths: Vec<String>,
+
+    /// Effective runtime culling setting of each entry in `flat_meshes`, after inheriting from its parents
+    pub flat_runtime_culling: Vec<bool>,
+
+    /// Tree path and `PolyMeta` of every visible node with children (ex: group-level material overrides), in depth-first order
+    pub group_metas: Vec<(String, PolyMeta)>,
+
+    /// World-space position of the local origin of the node that produced each entry in `flat_meshes` (see `origins`)
+    flat_origins: Vec<PolyVector>,
+
+    /// Size of the unit that `flat_meshes` is expressed in, in the same terms as `PolyMesh::unit_scale`. `None` keeps the scene's own units
+    target_unit: Option<f32>,
+
+    /// Cached result of `bounds`
+    bounds_cache: Cell<Option<Option<(PolyVector, PolyVector)>>>,
+
+    /// How the scene was read, so parts of it can be read again the same way
+    load_context: Arc<LoadContext>
+
+}
+
+/// Filter deciding which nodes have their geometry loaded (see `FlatPolyMesh::new_filtered`)
+type NodeFilter = Box<dyn Fn(&PolyMeta) -> bool + Send + Sync>;
+
+/// The options and filter a `FlatPolyMesh` was loaded with
+struct LoadContext {
+
+    /// Options passed to the reader
+    options: LoadOptions,
+
+    /// Nodes whose `PolyMeta` fails this have no geometry loaded
+    filter: Option<NodeFilter>
+
+}
+
+impl LoadContext {
+
+    /// Read the mesh in `mesh_path` as part of the scene at `scene_root`
+    fn read(&self, mesh_path: &Path, scene_root: &Path) -> Result<PolyMesh, PolyMeshError> {
+        match &self.filter {
+            Some(filter) => read_unpacked_subtree(mesh_path, scene_root, &self.options, filter),
+            None => read_unpacked_subtree(mesh_path, scene_root, &self.options, &|_| true)
+        }
+    }
+
+    /// Get the tree path of every node in the scene at `scene_root` that is read from the directory `mesh_path`
+    fn tree_paths_of(&self, mesh_path: &Path, scene_root: &Path) -> Result<Vec<String>, PolyMeshError> {
+        let target = fs::canonicalize(mesh_path).unwrap_or_else(|_| mesh_path.to_path_buf());
+        let mut tree_paths = Vec::new();
+        tree_paths_of_recursive(scene_root, scene_root, "", &target, &self.options, &mut HashSet::new(), &mut tree_paths)?;
+        Ok(tree_paths)
+    }
+
+}
+
+impl fmt::Debug for LoadContext {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("LoadContext")
+            .field("options", &self.options)
+            .field("filter", &self.filter.as_ref().map(|_| "Fn(&PolyMeta) -> bool"))
+            .finish()
+    }
+}
+
+impl FlatPolyMesh {
+
+    /// Load and flatten an unpacked PolyMesh.
+    ///
+    /// Geometry on the root itself is included, at tree path `/`, so a single-part asset with no children still loads as one mesh
+    ///
+    /// ```rust
+    /// use libpolymesh::prelude as pmf;
+    /// use tempdir::TempDir;
+    ///
+    /// let workspace = TempDir::new("leaf_root").unwrap();
+    /// let root = workspace.path();
+    /// std::fs::write(root.join("polymeta.json"), r#"{"version": 1.2, "type": "Geometry", "metadata": {}, "children": []}"#).unwrap();
+    /// std::fs::write(root.join("mesh.json"), r#"{"color": {"r": 0, "g": 255, "b": 0, "a": 255}, "triangles": [[{"x": 0, "y": 0, "z": 0}, {"x": 1, "y": 0, "z": 0}, {"x": 0, "y": 1, "z": 0}]]}"#).unwrap();
+    ///
+    /// let scene = pmf::FlatPolyMesh::new(root.to_str().unwrap()).unwrap();
+    /// assert_eq!(scene.flat_meshes.len(), 1);
+    /// assert_eq!(scene.flat_paths, vec!["/"]);
+    /// assert_eq!(pmf::get_flat_geometry(scene.root_mesh).len(), 1);
+    /// ```
+    pub fn new(root_path: &str) -> Result<Self, PolyMeshError> {
+        Self::load(root_path, LoadContext { options: LoadOptions::default(), filter: None }, None)
+    }
+
+    /// Load and flatten an unpacked PolyMesh, using custom `LoadOptions`.
+    ///
+    /// The options are kept, so `reload_subtree` and `load_deferred` read the scene the same way (ex: through the same `path_resolver`)
+    ///
+    /// ```rust
+    /// use libpolymesh::prelude as pmf;
+    /// use tempdir::TempDir;
+    ///
+    /// // A scene whose child has moved from "old" to "new"
+    /// let workspace = TempDir::new("resolver").unwrap();
+    /// let root = workspace.path();
+    /// std::fs::create_dir_all(root.join("new")).unwrap();
+    /// std::fs::write(root.join("polymeta.json"), r#"{"version": 1.2, "type": "Group", "metadata": {}, "children": [{"path": "/old", "translation": [0, 0, 0]}]}"#).unwrap();
+    /// std::fs::write(root.join("new/polymeta.json"), r#"{"version": 1.2, "type": "Geometry", "metadata": {}, "children": []}"#).unwrap();
+    /// std::fs::write(root.join("new/mesh.json"), r#"{"color": {"r": 0, "g": 255, "b": 0, "a": 255}, "triangles": [[{"x": 0, "y": 0, "z": 0}, {"x": 1, "y": 0, "z": 0}, {"x": 0, "y": 1, "z": 0}]]}"#).unwrap();
+    /// assert!(pmf::FlatPolyMesh::new(root.to_str().unwrap()).is_err());
+    ///
+    /// // Redirect the stale path while loading
+    /// let options = pmf::LoadOptions {
+    ///     path_resolver: Some(Box::new(|path: &str| path.replace("/old", "/new"))),
+    ///     ..Default::default()
+    /// };
+    /// let mut scene = pmf::FlatPolyMesh::new_with_options(root.to_str().unwrap(), options).unwrap();
+    /// assert_eq!(scene.flat_paths, vec!["/new"]);
+    ///
+    /// // Reloading keeps the redirect
+    /// scene.reload_subtree(root.join("polymeta.json").to_str().unwrap()).unwrap();
+    /// assert_eq!(scene.flat_paths, vec!["/new"]);
+    /// ```
+    pub fn new_with_options(root_path: &str, options: LoadOptions) -> Result<Self, PolyMeshError> {
+        Self::load(root_path, LoadContext { options, filter: None }, None)
+    }
+
+    /// Load and flatten an unpacked PolyMesh, converting its geometry into `target_unit`.
+    ///
+    /// Every flat mesh is scaled by the root's `unit_scale` (1.0 when unset) divided by `target_unit`,
+    /// so a scene authored in centimeters (`unit_scale` of 0.01) loaded with a `target_unit` of 1.0 ends up in meters.
+    /// `root_mesh` itself is left in the scene's own units. A `unit_scale` that is not a positive finite number fails with `PolyMeshError::InvalidDimensions`
+    ///
+    /// ```rust
+    /// use libpolymesh::prelude as pmf;
+    /// use libpolymesh::common::PolyMeshError;
+    /// use tempdir::TempDir;
+    ///
+    /// let workspace = TempDir::new("units").unwrap();
+    /// let root = workspace.path();
+    /// std::fs::write(root.join("mesh.json"), r#"{"color": {"r": 0, "g": 255, "b": 0, "a": 255}, "triangles": [[{"x": 0, "y": 0, "z": 0}, {"x": 100, "y": 0, "z": 0}, {"x": 0, "y": 100, "z": 0}]]}"#).unwrap();
+    ///
+    /// // Centimeters become meters
+    /// std::fs::write(root.join("polymeta.json"), r#"{"version": 1.2, "type": "Geometry", "metadata": {}, "children": [], "unit_scale": 0.01}"#).unwrap();
+    /// let scene = pmf::FlatPolyMesh::new_in_units(root.to_str().unwrap(), 1.0).unwrap();
+    /// assert!((scene.flat_meshes[0].triangles.as_ref().unwrap()[0][1].x - 1.0).abs() < 1e-6);
+    ///
+    /// // A unit with no size is rejected
+    /// std::fs::write(root.join("polymeta.json"), r#"{"version": 1.2, "type": "Geometry", "metadata": {}, "children": [], "unit_scale": 0}"#).unwrap();
+    /// assert!(matches!(pmf::FlatPolyMesh::new_in_units(root.to_str().unwrap(), 1.0), Err(PolyMeshError::InvalidDimensions(_))));
+    /// ```
+    pub fn new_in_units(root_path: &str, target_unit: f32) -> Result<Self, PolyMeshError> {
+        if !target_unit.is_finite() || target_unit <= 0.0 {
+            return Err(PolyMeshError::InvalidDimensions(format!("Target unit must be positive, got {}", target_unit)));
+        }
+        Self::load(root_path, LoadContext { options: LoadOptions::default(), filter: None }, Some(target_unit))
+    }
+
+    /// Load and flatten an unpacked PolyMesh, only reading geometry for nodes whose `PolyMeta` passes `filter`.
+    ///
+    /// The filter is checked before a node's `mesh.json` is read, so rejected nodes cost nothing beyond their `polymeta.json`.
+    /// Children of rejected nodes are still visited. The filter is kept, and applied again by `reload_subtree` and `load_deferred`
+    pub fn new_filtered(root_path: &str, filter: impl Fn(&PolyMeta) -> bool + Send + Sync + 'static) -> Result<Self, PolyMeshError> {
+        Self::load(root_path, LoadContext { options: LoadOptions::default(), filter: Some(Box::new(filter)) }, None)
+    }
+
+    fn load(root_path: &str, load_context: LoadContext, target_unit: Option<f32>) -> Result<Self, PolyMeshError> {
+        let root_mesh = load_context.read(Path::new(root_path), Path::new(root_path))?;
+        Ok(Self::from_root_mesh(root_path, root_mesh, target_unit, Arc::new(load_context)))
+    }
+
+    fn from_root_mesh(root_path: &str, root_mesh: PolyMesh, target_unit: Option<f32>, load_context: Arc<LoadContext>) -> Self {
+        let mut output = Self {
+            root_path: PathBuf::from(root_path),
+            root_mesh,
+            flat_meshes: Vec::new(),
+            flat_paths: Vec::new(),
+            flat_runtime_culling: Vec::new(),
+            group_metas: Vec::new(),
+            flat_origins: Vec::new(),
+            target_unit,
+            bounds_cache: Cell::new(None),
+            load_context
+        };
+        output.reflatten();
+        output
+    }
+
+    /// Rebuild `flat_meshes`, `flat_paths`, `flat_runtime_culling`, `group_metas`, and `origins` from `root_mesh`, reusing their existing allocations.
+    ///
+    /// Call this after editing `root_mesh` directly
+    pub fn reflatten(&mut self) {
+        self.flat_meshes.clear();
+        self.flat_paths.clear();
+        self.flat_runtime_culling.clear();
+        self.flat_origins.clear();
+
+        // Flatten the tree
+        let factor = self.unit_factor();
+        let (flat_meshes, flat_paths, flat_runtime_culling, flat_origins) = (&mut self.flat_meshes, &mut self.flat_paths, &mut self.flat_runtime_culling, &mut self.flat_origins);
+        flatten_tree(&self.root_mesh, &mut |path, _, world, culling, geometry| {
+            flat_paths.push(normalize_tree_path(path));
+            flat_runtime_culling.push(culling);
+            flat_origins.push(world.transform_point(PolyVector::zero()) * factor);
+            flat_meshes.push(geometry);
+        });
+        scale_meshes(&mut self.flat_meshes, factor);
+        self.group_metas.clear();
+        collect_group_metas(&self.root_mesh, "", &mut self.group_metas);
+        self.invalidate_bounds();
+    }
+
+    /// Get the world-space position of the local origin (ex: where to draw an editor gizmo) of the node that produced each entry in `flat_meshes`.
+    ///
+    /// These are in the same units as `flat_meshes`, and follow `sort_stable` and `reload_subtree`. Geometry on the root itself has its origin at zero
+    pub fn origins(&self) -> &[PolyVector] {
+        &self.flat_origins
+    }
+
+    /// Get the `PolyMeta` of the group at `path`, if it is listed in `group_metas`
+    pub fn group_meta(&self, path: &str) -> Option<&PolyMeta> {
+        let path = normalize_tree_path(path);
+        self.group_metas.iter().find(|(group_path, _)| *group_path == path).map(|(_, meta)| meta)
+    }
+
+    /// Get the factor that converts the scene's own units into the target unit
+    fn unit_factor(&self) -> f32 {
+        match self.target_unit {
+            Some(target_unit) => self.root_mesh.unit_scale.unwrap_or(1.0) / target_unit,
+            None => 1.0
+        }
+    }
+
+    /// Reorder the flattened meshes by their `flat_paths` entry, compared as plain strings.
+    ///
+    /// Entries that share a path (ex: several references to the same child) keep their relative order, so the result
+    /// only depends on the scene's contents rather than the order it was loaded in
+    pub fn sort_stable(&mut self) {
+        let mut order: Vec<usize> = (0..self.flat_paths.len()).collect();
+        order.sort_by(|a, b| self.flat_paths[*a].cmp(&self.flat_paths[*b]));
+
+        // Rebuild every list in the new order
+        let mut meshes: Vec<Option<MeshDef>> = self.flat_meshes.drain(..).map(Some).collect();
+        self.flat_meshes = order.iter().map(|i| meshes[*i].take().unwrap()).collect();
+        self.flat_paths = order.iter().map(|i| self.flat_paths[*i].clone()).collect();
+        self.flat_runtime_culling = order.iter().map(|i| self.flat_runtime_culling[*i]).collect();
+        self.flat_origins = order.iter().map(|i| self.flat_origins[*i]).collect();
+    }
+
+    /// Merge `flat_meshes` into one mesh per material, for drawing each material in a single call. Meshes with no material are merged under `None`.
+    ///
+    /// Meshes are merged in `flat_meshes` order (see `MeshDef::merge`), so any that share a material but differ in color, `double_sided`,
+    /// skinning, or texture coordinates fail with `PolyMeshError::IncompatibleMeshes`
+    pub fn merge_by_material(&self) -> Result<HashMap<Option<String>, MeshDef>, PolyMeshError> {
+        let mut merged: HashMap<Option<String>, MeshDef> = HashMap::new();
+        for mesh in &self.flat_meshes {
+            match merged.get_mut(&mesh.material) {
+                Some(batch) => batch.merge(mesh)?,
+                None => {
+                    merged.insert(mesh.material.clone(), mesh.clone());
+                }
+            }
+        }
+        Ok(merged)
+    }
+
+    /// Apply `f` to every entry in `flat_meshes`.
+    ///
+    /// With the `parallel` feature, the meshes are split evenly across one thread per available core. Otherwise they are handled in order on the calling thread
+    pub fn map_meshes<F: Fn(&mut MeshDef) + Sync>(&mut self, f: F) {
+        #[cfg(feature = "parallel")]
+        {
+            let threads = thread::available_parallelism().map_or(1, |count| count.get());
+            let chunk_size = self.flat_meshes.len().div_ceil(threads).max(1);
+            let f = &f;
+            thread::scope(|scope| {
+                for chunk in self.flat_meshes.chunks_mut(chunk_size) {
+                    scope.spawn(move || chunk.iter_mut().for_each(f));
+                }
+            });
+        }
+
+        #[cfg(not(feature = "parallel"))]
+        self.flat_meshes.iter_mut().for_each(f);
+
+        self.invalidate_bounds();
+    }
+
+    /// Iterate every world-space vertex in `flat_meshes`, three per triangle, without collecting them into a new list
+    pub fn world_vertices(&self) -> impl Iterator<Item = PolyVector> + '_ {
+        self.flat_meshes.iter().flat_map(|mesh| mesh.triangles.iter().flatten()).flatten().copied()
+    }
+
+    /// Get the world-space bounding box of the whole scene as `(min, max)`, or `None` if it has no vertices.
+    ///
+    /// The result is cached after the first call. Methods on this type that change the geometry reset the cache,
+    /// but code that edits `flat_meshes` directly must call `invalidate_bounds` afterwards
+    pub fn bounds(&self) -> Option<(PolyVector, PolyVector)> {
+        if let Some(bounds) = self.bounds_cache.get() {
+            return bounds;
+        }
+
+        // Union the bounds of every mesh. Min and max do not depend on order, so splitting the work gives the same result
+        #[cfg(feature = "parallel")]
+        let bounds = {
+            let threads = thread::available_parallelism().map_or(1, |count| count.get());
+            let chunk_size = self.flat_meshes.len().div_ceil(threads).max(1);
+            let chunk_bounds: Vec<Option<(PolyVector, PolyVector)>> = thread::scope(|scope| {
+                let handles: Vec<_> = self.flat_meshes.chunks(chunk_size).map(|chunk| scope.spawn(move || union_bounds(chunk.iter().filter_map(MeshDef::bounds)))).collect();
+                handles.into_iter().map(|handle| handle.join().expect("Bounds thread panicked")).collect()
+            });
+            union_bounds(chunk_bounds.into_iter().flatten())
+        };
+
+        #[cfg(not(feature = "parallel"))]
+        let bounds = union_bounds(self.flat_meshes.iter().filter_map(MeshDef::bounds));
+
+        self.bounds_cache.set(Some(bounds));
+        bounds
+    }
+
+    /// Forget the cached result of `bounds`
+    pub fn invalidate_bounds(&self) {
+        self.bounds_cache.set(None);
+    }
+
+    /// Get the directory that the entry at `index` in `flat_meshes` was loaded from (the one holding its `mesh.json`).
+    ///
+    /// This maps the tree path straight onto `root_path`, so it assumes every reference on the way is `PathMode::RelativeToParent`
+    pub fn source_path(&self, index: usize) -> Option<PathBuf> {
+        self.flat_paths.get(index).map(|path| make_child_file_path(&self.root_path, path))
+    }
+
+    /// Load and flatten several independent scenes concurrently, using one thread per scene.
+    ///
+    /// Results are returned in the same order as `roots`, so one failing scene does not affect the others
+    pub fn load_many(roots: &[&str]) -> Vec<Result<Self, PolyMeshError>> {
+        thread::scope(|scope| {
+            let handles: Vec<_> = roots.iter().map(|root| scope.spawn(move || Self::new(root))).collect();
+            handles.into_iter().map(|handle| handle.join().expect("Scene loader thread panicked")).collect()
+        })
+    }
+
+    /// List every `polymeta.json` and `mesh.json` file that the scene at `root_path` depends on, without loading any `mesh.json` files.
+    ///
+    /// Each directory is only visited once, so this terminates even if the reference graph contains cycles. A missing file results in an error
+    pub fn manifest(root_path: &str) -> Result<Vec<String>, PolyMeshError> {
+        let mut files = Vec::new();
+        let mut visited = HashSet::new();
+        manifest_recursive(Path::new(root_path), Path::new(root_path), &mut visited, &mut files)?;
+        Ok(files)
+    }
+
+    /// Re-read the part of the scene affected by a change to `changed_path`, without re-flattening the entire scene.
+    ///
+    /// `changed_path` may point to a `polymeta.json`, a `mesh.json`, or a mesh directory. Every node read from that directory is
+    /// found by following the scene's references from `root_path` again, so directories only reached through a
+    /// `PathMode::RelativeToRoot` reference (ex: a shared asset) are found at each tree path they are used at. Every such node is
+    /// re-read from disk, and every entry in `flat_meshes` whose `flat_paths` entry is one of those paths or lies beneath it is
+    /// replaced. The replacement entries are inserted where the first replaced entry used to be. The subtree is read with the
+    /// same options and filter as the rest of the scene, and fails with `PolyMeshError::UnresolvedPath` if no node uses the directory
+    ///
+    /// ```rust
+    /// use libpolymesh::prelude as pmf;
+    /// use tempdir::TempDir;
+    ///
+    /// let workspace = TempDir::new("reload").unwrap();
+    /// let root = workspace.path();
+    /// let write = |dir: &str, file: &str, json: &str| {
+    ///     std::fs::create_dir_all(root.join(dir)).unwrap();
+    ///     std::fs::write(root.join(dir).join(file), json).unwrap();
+    /// };
+    /// let triangle = r#"{"color": {"r": 0, "g": 255, "b": 0, "a": 255}, "triangles": [[{"x": 0, "y": 0, "z": 0}, {"x": 1, "y": 0, "z": 0}, {"x": 0, "y": 1, "z": 0}]]}"#;
+    ///
+    /// // A group holding one child next to it, and one shared from the scene root
+    /// write("", "polymeta.json", r#"{"version": 1.2, "type": "Group", "metadata": {}, "children": [{"path": "/group", "translation": [0, 0, 0]}]}"#);
+    /// write("group", "polymeta.json", r#"{"version": 1.2, "type": "Group", "metadata": {}, "children": [
+    ///     {"path": "/local", "translation": [0, 0, 0]},
+    ///     {"path": "/shared/triangle", "path_mode": "RelativeToRoot", "translation": [0, 0, 1]}
+    /// ]}"#);
+    /// for dir in ["group/local", "shared/triangle"] {
+    ///     write(dir, "polymeta.json", r#"{"version": 1.2, "type": "Geometry", "metadata": {}, "children": []}"#);
+    ///     write(dir, "mesh.json", triangle);
+    /// }
+    ///
+    /// let mut scene = pmf::FlatPolyMesh::new(root.to_str().unwrap()).unwrap();
+    /// scene.reload_subtree(root.join("group").to_str().unwrap()).unwrap();
+    /// assert_eq!(scene.flat_paths, vec!["/group/local", "/group/shared/triangle"]);
+    ///
+    /// // The shared mesh is only reached through the group, but is still found where it is used
+    /// write("shared/triangle", "mesh.json", &triangle.replace("\"x\": 1", "\"x\": 2"));
+    /// scene.reload_subtree(root.join("shared/triangle/mesh.json").to_str().unwrap()).unwrap();
+    /// assert_eq!(scene.flat_paths, vec!["/group/local", "/group/shared/triangle"]);
+    /// assert_eq!(scene.flat_meshes[1].triangles.as_ref().unwrap()[0][1].x, 2.0);
+    /// ```
+    pub fn reload_subtree(&mut self, changed_path: &str) -> Result<(), PolyMeshError> {
+
+        // Find the mesh directory that changed
+        let mut changed_dir = Path::new(changed_path);
+        if changed_dir.ends_with("polymeta.json") || changed_dir.ends_with("mesh.json") {
+            changed_dir = changed_dir.parent().unwrap_or(changed_dir);
+        }
+
+        // A change to the root means everything needs to be reloaded
+        let canonical = |path: &Path| fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+        if canonical(changed_dir) == canonical(&self.root_path) {
+            let root_mesh = self.load_context.read(&self.root_path, &self.root_path)?;
+            let root_path = self.root_path.to_string_lossy().to_string();
+            *self = Self::from_root_mesh(&root_path, root_mesh, self.target_unit, self.load_context.clone());
+            return Ok(());
+        }
+
+        // Find everywhere the directory is used
+        let tree_paths = self.load_context.tree_paths_of(changed_dir, &self.root_path)?;
+        if tree_paths.is_empty() {
+            return Err(PolyMeshError::UnresolvedPath(changed_path.to_string()));
+        }
+
+        // Re-read the changed subtree as part of the whole scene, so references relative to the root still resolve
+        let new_mesh = self.load_context.read(changed_dir, &self.root_path)?;
+        for tree_path in tree_paths {
+            self.replace_subtree(&tree_path, &new_mesh);
+        }
+        Ok(())
+    }
+
+    /// Load the deferred mesh at the tree `path` (see `PolyChildReference::deferred`), swapping out its placeholder and adding its geometry to `flat_meshes`.
+    ///
+    /// Every node at `path` is replaced, and the new entries are placed as in `reload_subtree`. Deferred meshes further down stay as placeholders.
+    /// Does nothing if the mesh at `path` is already loaded, and fails with `PolyMeshError::UnresolvedPath` if there is none
+    pub fn load_deferred(&mut self, path: &str) -> Result<(), PolyMeshError> {
+        let tree_path = normalize_tree_path(path);
+        let source = match self.root_mesh.resolve_path(&tree_path) {
+            Some(chain) => chain.last().and_then(|child| child.mesh.deferred_source.clone()),
+            None => return Err(PolyMeshError::UnresolvedPath(path.to_string()))
+        };
+
+        // Read the real mesh in place of the placeholder, as part of the whole scene
+        if let Some(source) = source {
+            let new_mesh = self.load_context.read(&source.path, &self.root_path)?;
+            self.replace_subtree(&tree_path, &new_mesh);
+        }
+        Ok(())
+    }
+
+    /// Swap `new_mesh` in for every node at `tree_path`, re-flattening just the replaced nodes. Returns `false` if no node is at `tree_path`
+    fn replace_subtree(&mut self, tree_path: &str, new_mesh: &PolyMesh) -> bool {
+
+        // Swap the subtree into the hierarchy, collecting the absolute transform of every replaced node
+        let mut replaced = Vec::new();
+        let root_culling = Some(self.root_mesh.resolve_runtime_culling(false)).filter(|_| self.root_mesh.visible);
+        if !replace_subtree_recursive(&mut self.root_mesh, "", &PolyMatrix4::identity(), root_culling, tree_path, new_mesh, &mut replaced) {
+            return false;
+        }
+
+        // Remove the stale flat meshes
+        let prefix = format!("{}/", tree_path);
+        let mut insert_at = None;
+        let mut i = 0;
+        while i < self.flat_paths.len() {
+            if self.flat_paths[i] == tree_path || self.flat_paths[i].starts_with(&prefix) {
+                self.flat_paths.remove(i);
+                self.flat_runtime_culling.remove(i);
+                self.flat_origins.remove(i);
+                self.flat_meshes.remove(i);
+                insert_at.get_or_insert(i);
+            } else {
+                i += 1;
+            }
+        }
+
+        // Flatten just the replaced subtrees
+        let mut new_paths = Vec::new();
+        let mut new_culling = Vec::new();
+        let mut new_origins = Vec::new();
+        let mut new_meshes = Vec::new();
+        let factor = self.unit_factor();
+        for (node, world, culling) in &replaced {
+            let mut emit = |path: &str, _: &TransPolyMeshPtr, world: &PolyMatrix4, culling, geometry| {
+                new_paths.push(normalize_tree_path(path));
+                new_culling.push(culling);
+                new_origins.push(world.transform_point(PolyVector::zero()) * factor);
+                new_meshes.push(geometry);
+            };
+            if let Some(geometry) = node.mesh.geometry.as_ref().filter(|_| node.mesh.has_renderable_geometry()) {
+                emit(tree_path, node, world, *culling, geometry.transformed_by_matrix(world));
+            }
+            get_flat_geometry_recursive(&node.mesh, tree_path, world, *culling, &mut emit);
+        }
+
+        // Splice the new flat meshes in
+        scale_meshes(&mut new_meshes, factor);
+        let insert_at = insert_at.unwrap_or(self.flat_meshes.len());
+        self.flat_paths.splice(insert_at..insert_at, new_paths);
+        self.flat_runtime_culling.splice(insert_at..insert_at, new_culling);
+        self.flat_origins.splice(insert_at..insert_at, new_origins);
+        self.flat_meshes.splice(insert_at..insert_at, new_meshes);
+        self.invalidate_bounds();
+
+        // Group metadata is cheap to rebuild, so redo all of it
+        self.group_metas.clear();
+        collect_group_metas(&self.root_mesh, "", &mut self.group_metas);
+
+        true
+    }
+
+}
+
+/// Get the bounding box that contains every one of `boxes`, or `None` if there are none
+fn union_bounds(boxes: impl Iterator<Item = (PolyVector, PolyVector)>) -> Option<(PolyVector, PolyVector)> {
+    bounds_of_points(boxes.flat_map(|(min, max)| [min, max]))
+}
+
+/// Add the tree path and `PolyMeta` of `mesh` and every visible node under it that has children to `out`
+fn collect_group_metas(mesh: &PolyMesh, path: &str, out: &mut Vec<(String, PolyMeta)>) {
+    if !mesh.visible || mesh.children.is_empty() {
+        return;
+    }
+    out.push((normalize_tree_path(path), mesh.to_poly_meta()));
+    for child in &mesh.children {
+        collect_group_metas(&child.mesh, &format!("{}{}", path, child.path), out);
+    }
+}
+
+/// Uniformly scale every mesh in `meshes` about the origin
+fn scale_meshes(meshes: &mut [MeshDef], factor: f32) {
+    if factor == 1.0 {
+        return;
+    }
+    for mesh in meshes.iter_mut() {
+        mesh.scale(PolyVector::unit() * factor);
+    }
+}
+
+/// Swap `new_mesh` in for every node at `target`, collecting the pointer, world matrix, and culling setting of each visible one.
+///
+/// `inherited_culling` is `None` if `root_mesh` or one of its parents is hidden. Returns `false` if no node is at `target`
+fn replace_subtree_recursive(root_mesh: &mut PolyMesh, root_path: &str, parent_matrix: &PolyMatrix4, inherited_culling: Option<bool>, target: &str, new_mesh: &PolyMesh, replaced: &mut Vec<(TransPolyMeshPtr, PolyMatrix4, bool)>) -> bool {
+    let mut found = false;
+    for child in root_mesh.children.iter_mut() {
+        let child_path = normalize_tree_path(&format!("{}{}", root_path, child.path));
+        let world = *parent_matrix * child.transform.to_matrix();
+
+        if child_path == target {
+
+            // Replace this node, and track where it sits in the world and how it is culled (if it will be flattened at all).
+            // Being deferred is part of the reference rather than the mesh, so that is kept
+            let deferred = child.mesh.deferred;
+            *child.mesh = new_mesh.clone();
+            child.mesh.deferred = deferred;
+            found = true;
+            if let Some(inherited_culling) = inherited_culling.filter(|_| new_mesh.visible) {
+                replaced.push((child.clone(), world, new_mesh.resolve_runtime_culling(inherited_culling)));
+            }
+
+        } else if target.starts_with(&format!("{}/", child_path)) {
+
+            // The target is somewhere under this child
+            let child_culling = inherited_culling.filter(|_| child.mesh.visible).map(|culling| child.mesh.resolve_runtime_culling(culling));
+            found |= replace_subtree_recursive(&mut child.mesh, &child_path, &world, child_culling, target, new_mesh, replaced);
+
+        }
+    }
+    found
+}
+
+/// Collect the tree path of every node beneath `root_path` (at `tree_path`) that is read from the directory `target`, following
+/// references the same way the loader does
+fn tree_paths_of_recursive(root_path: &Path, scene_root: &Path, tree_path: &str, target: &Path, options: &LoadOptions, in_progress: &mut HashSet<PathBuf>, tree_paths: &mut Vec<String>) -> Result<(), PolyMeshError> {
+
+    // The loader rejects cycles, so there is nothing to find inside one
+    let directory = fs::canonicalize(root_path).unwrap_or_else(|_| root_path.to_path_buf());
+    if !in_progress.insert(directory.clone()) {
+        return Ok(());
+    }
+
+    let polymeta: PolyMeta = read_json_file(&make_polymeta_file_path(root_path))?;
+    for child in &polymeta.children {
+
+        // Redirect the child just like the loader would
+        let mut child = child.clone();
+        if let Some(resolver) = &options.path_resolver {
+            child.path = resolver(&child.path);
+        }
+        let child_path = make_reference_file_path(root_path, scene_root, &child);
+        let child_tree_path = normalize_tree_path(&format!("{}{}", tree_path, child.path));
+
+        let is_target = fs::canonicalize(&child_path).unwrap_or_else(|_| child_path.clone()) == target;
+        if is_target && !tree_paths.contains(&child_tree_path) {
+            tree_paths.push(child_tree_path.clone());
+        }
+        tree_paths_of_recursive(&child_path, scene_root, &child_tree_path, target, options, in_progress, tree_paths)?;
+    }
+
+    in_progress.remove(&directory);
+    Ok(())
+}
+
+fn manifest_recursive(root_path: &Path, scene_root: &Path, visited: &mut HashSet<PathBuf>, files: &mut Vec<String>) -> Result<(), PolyMeshError> {
+
+    // Skip directories that have already been handled
+    if !visited.insert(fs::canonicalize(root_path).unwrap_or_else(|_| root_path.to_path_buf())) {
+        return Ok(());
+    }
+
+    // Read the polymeta
+    let polymeta_path = make_polymeta_file_path(root_path);
+    let polymeta: PolyMeta = read_json_file(&polymeta_path)?;
+    files.push(polymeta_path.display().to_string());
+
+    // Track the mesh.json if the loader would read one
+    if polymeta.geometry.is_none() && (polymeta.mesh_type == MeshType::Geometry || polymeta.mesh_type == MeshType::GeoGroup) {
+        let mesh_path = make_mesh_file_path(root_path);
+        if !mesh_path.is_file() {
+            return Err(PolyMeshError::Io(io::Error::new(io::ErrorKind::NotFound, format!("Missing mesh.json: {}", mesh_path.display()))));
+        }
+        files.push(mesh_path.display().to_string());
+    }
+
+    // Handle every child
+    for child in &polymeta.children {
+        manifest_recursive(&make_reference_file_path(root_path, scene_root, child), scene_root, visited, files)?;
+    }
+
+    Ok(())
+}
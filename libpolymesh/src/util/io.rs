@@ -3,19 +3,28 @@ extern crate tempdir;
 use crate::{
     read::{
         unpack_pmf,
-        read_unpacked_polymesh
+        read_unpacked_polymesh_with_options,
+        LoadOptions
     },
     write::{
-        write_unpacked_polymesh,
-        pack_pmf
+        write_unpacked_polymesh_with_options,
+        pack_pmf,
+        WriteOptions
     },
-    common::PolyMesh
+    common::{
+        PolyMesh,
+        PolyMeshError
+    }
 };
 use tempdir::TempDir;
-use serde_json::Result;
 
 /// Read a PMF file into a PolyMesh object
-pub fn read_pmf(file_path: &str) -> Result<PolyMesh> {
+pub fn read_pmf(file_path: &str) -> Result<PolyMesh, PolyMeshError> {
+    read_pmf_with_options(file_path, &LoadOptions::default())
+}
+
+/// Read a PMF file into a PolyMesh object, using custom `LoadOptions`
+pub fn read_pmf_with_options(file_path: &str, options: &LoadOptions) -> Result<PolyMesh, PolyMeshError> {
 
     // Set up a workspace directory
     let workspace = TempDir::new("libpolymesh").unwrap();
@@ -25,19 +34,24 @@ pub fn read_pmf(file_path: &str) -> Result<PolyMesh> {
     let _ = unpack_pmf(file_path, workspace_path).unwrap();
 
     // Load the PMF into memory and return it
-    return read_unpacked_polymesh(workspace_path);
+    return read_unpacked_polymesh_with_options(workspace_path, options);
 
 }
 
 /// Write a PolyMesh to a .pmf file
 pub fn write_pmf(mesh: &PolyMesh, output_path: &str) {
+    write_pmf_with_options(mesh, output_path, &WriteOptions::default());
+}
+
+/// Write a PolyMesh to a .pmf file, using custom `WriteOptions`
+pub fn write_pmf_with_options(mesh: &PolyMesh, output_path: &str, options: &WriteOptions) {
 
     // Set up a workspace directory
     let workspace = TempDir::new("libpolymesh").unwrap();
     let workspace_path = &workspace.path().to_str().unwrap();
 
     // Write the mesh
-    let _ = write_unpacked_polymesh(mesh, workspace_path).unwrap();
+    let _ = write_unpacked_polymesh_with_options(mesh, workspace_path, options).unwrap();
 
     // Pack the mesh
     let _ = pack_pmf(workspace_path, output_path).unwrap();
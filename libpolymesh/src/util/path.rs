@@ -1,12 +1,39 @@
+use crate::common::serialization::data::polymeta::{
+    PolyChildReference,
+    PathMode
+};
+use std::path::{
+    Path,
+    PathBuf
+};
 
-pub fn make_polymeta_file_path(root: &str) -> String {
-    return format!("{}/polymeta.json", root).to_string();
+pub fn make_polymeta_file_path(root: &Path) -> PathBuf {
+    root.join("polymeta.json")
 }
 
-pub fn make_mesh_file_path(root: &str) -> String {
-    return format!("{}/mesh.json", root).to_string();
+pub fn make_mesh_file_path(root: &Path) -> PathBuf {
+    root.join("mesh.json")
 }
 
-pub fn make_child_file_path(root: &str, child: &str) -> String {
-    return format!("{}{}", root, child).to_string();
-}
\ No newline at end of file
+/// Resolve a logical, forward-slash child path (ex: `/faces_gr/positive_x_geo`) against a parent directory
+pub fn make_child_file_path(root: &Path, child: &str) -> PathBuf {
+    let mut output = root.to_path_buf();
+    for segment in child.split('/').filter(|segment| !segment.is_empty()) {
+        output.push(segment);
+    }
+    output
+}
+
+/// Find the directory of the child that `child` refers to, given the directory of the `polymeta.json` listing it and the scene's root directory
+pub fn make_reference_file_path(parent: &Path, scene_root: &Path, child: &PolyChildReference) -> PathBuf {
+    match child.path_mode {
+        PathMode::RelativeToParent => make_child_file_path(parent, &child.path),
+        PathMode::RelativeToRoot => make_child_file_path(scene_root, &child.path)
+    }
+}
+
+/// Normalize a logical tree path so equivalent paths compare equal (ex: `faces_gr//positive_x_geo/` becomes `/faces_gr/positive_x_geo`)
+pub fn normalize_tree_path(path: &str) -> String {
+    let segments: Vec<&str> = path.split('/').filter(|segment| !segment.is_empty()).collect();
+    format!("/{}", segments.join("/"))
+}
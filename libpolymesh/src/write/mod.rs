@@ -2,6 +2,11 @@
 
 mod write;
 mod pack;
+mod options;
 
-pub use write::write_unpacked_polymesh;
+pub use write::{
+    write_unpacked_polymesh,
+    write_unpacked_polymesh_with_options
+};
+pub use options::WriteOptions;
 pub use pack::pack_pmf;
\ No newline at end of file
@@ -0,0 +1,37 @@
+use crate::export::ExportPrecision;
+use serde::Serialize;
+use serde_json::Result;
+
+/// Options controlling how a PolyMesh is written to disk
+#[derive(Debug, Clone, PartialEq)]
+pub struct WriteOptions {
+
+    /// Pretty-print JSON files for human readability. Disabling this produces smaller, compact files
+    pub pretty: bool,
+
+    /// Optionally round every vertex in `mesh.json` files to a number of decimal places, producing smaller files
+    pub precision: Option<ExportPrecision>
+
+}
+
+impl Default for WriteOptions {
+    fn default() -> Self {
+        Self {
+            pretty: true,
+            precision: None
+        }
+    }
+}
+
+impl WriteOptions {
+
+    /// Serialize a value to a JSON string, respecting these options
+    pub fn to_json<T: Serialize>(&self, value: &T) -> Result<String> {
+        if self.pretty {
+            serde_json::to_string_pretty(value)
+        } else {
+            serde_json::to_string(value)
+        }
+    }
+
+}
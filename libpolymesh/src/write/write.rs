@@ -1,59 +1,165 @@
 use super::super::{
-    common::mesh::PolyMesh,
+    common::{
+        mesh::{
+            PolyMesh,
+            MeshType
+        },
+        serialization::data::polymeta::PathMode
+    },
     util::{
         make_polymeta_file_path,
         make_mesh_file_path,
         make_child_file_path
     }
 };
+use super::WriteOptions;
+use std::collections::HashMap;
+use std::collections::hash_map::DefaultHasher;
 use std::fs;
+use std::hash::{
+    Hash,
+    Hasher
+};
+use std::path::Path;
 use serde_json::Result;
 
-/// Write a PolyMesh to a root directory in "unpacked" mode. This assumes that the `root_path` is an empty directory
+/// Every subtree written so far, so repeated instances can point at the first copy instead of being written again
+#[derive(Default)]
+struct InstanceTable<'a> {
+
+    /// Written subtrees and the tree paths they were written to, grouped by the key of their contents
+    written: HashMap<u64, Vec<(&'a PolyMesh, String)>>,
+
+    /// The key of every subtree seen so far, so each node is only hashed once
+    keys: HashMap<*const PolyMesh, u64>
+
+}
+
+impl<'a> InstanceTable<'a> {
+
+    /// Get a key that is the same for every subtree equal to `mesh`
+    fn key(&mut self, mesh: &'a PolyMesh) -> u64 {
+        if let Some(key) = self.keys.get(&(mesh as *const PolyMesh)) {
+            return *key;
+        }
+
+        // Hash this node's own data, then the keys of its children in order
+        let mut hasher = DefaultHasher::new();
+        mesh.to_poly_meta().to_canonical_json().hash(&mut hasher);
+        mesh.geometry.as_ref().map(|geometry| geometry.to_canonical_json()).hash(&mut hasher);
+        for child in &mesh.children {
+            self.key(child.mesh.as_ref()).hash(&mut hasher);
+        }
+        let key = hasher.finish();
+        self.keys.insert(mesh as *const PolyMesh, key);
+        key
+    }
+
+    /// Get the tree path a subtree equal to `mesh` was written to, if there is one
+    fn find(&mut self, mesh: &'a PolyMesh) -> Option<String> {
+        let key = self.key(mesh);
+        self.written.get(&key)?.iter().find(|(other, _)| *other == mesh).map(|(_, path)| path.clone())
+    }
+
+    /// Record that `mesh` was written to the tree path `path`
+    fn insert(&mut self, mesh: &'a PolyMesh, path: String) {
+        let key = self.key(mesh);
+        self.written.entry(key).or_default().push((mesh, path));
+    }
+
+}
+
+/// Write a PolyMesh to a root directory in "unpacked" mode. This assumes that the `root_path` is an empty directory.
+///
+/// Identical subtrees anywhere in the scene are written to a single directory. Every later instance references the first copy
+/// with `PathMode::RelativeToRoot`, so it is only written once
+///
+/// ```rust
+/// use libpolymesh::prelude as pmf;
+/// use libpolymesh::common::{PolyMesh, MeshType, TransPolyMeshPtr};
+/// use libpolymesh::write::write_unpacked_polymesh;
+/// use tempdir::TempDir;
+///
+/// // The same quad, used once inside a group and once at the root
+/// let quad = || pmf::make_quad(pmf::PolyVector::unit_y(), pmf::PolyVector::unit(), pmf::PolyVector::zero(), pmf::PolyVector::unit_x(), pmf::PolyColor::green());
+/// let mut group = PolyMesh::new(MeshType::Group, None);
+/// group.add_child(TransPolyMeshPtr::new("/quad".to_string(), quad(), None));
+/// let mut root = PolyMesh::new(MeshType::Group, None);
+/// root.add_child(TransPolyMeshPtr::new("/group".to_string(), group, None));
+/// root.add_child(TransPolyMeshPtr::new("/copy".to_string(), quad(), None));
+///
+/// let workspace = TempDir::new("instances").unwrap();
+/// write_unpacked_polymesh(&root, workspace.path().to_str().unwrap()).unwrap();
+/// assert!(workspace.path().join("copy/mesh.json").exists());
+/// assert!(!workspace.path().join("group/quad").exists());
+///
+/// // Both instances still read back, with the one inside the group named after the copy it uses
+/// let scene = pmf::FlatPolyMesh::new(workspace.path().to_str().unwrap()).unwrap();
+/// assert_eq!(scene.flat_paths, vec!["/group/copy", "/copy"]);
+/// ```
 pub fn write_unpacked_polymesh(mesh: &PolyMesh, root_path: &str) -> Result<()> {
+    write_unpacked_polymesh_with_options(mesh, root_path, &WriteOptions::default())
+}
 
-    // Create a list to keep track of already written meshes (this prevents infinite write lock)
-    let mut written_children = Vec::new();
+/// Write a PolyMesh to a root directory in "unpacked" mode, using custom `WriteOptions`
+pub fn write_unpacked_polymesh_with_options(mesh: &PolyMesh, root_path: &str, options: &WriteOptions) -> Result<()> {
+
+    // Keep track of already written subtrees for the whole scene
+    let mut instances = InstanceTable::default();
 
     // Handle writing
-    return write_unpacked_polymesh_recursive(mesh, root_path, &mut written_children);
+    write_unpacked_polymesh_recursive(mesh, Path::new(root_path), "", options, &mut instances)
 
 }
 
-fn write_unpacked_polymesh_recursive(mesh: &PolyMesh, root_path: &str, written_children: &mut Vec<String>) -> Result<()> {
+fn write_unpacked_polymesh_recursive<'a>(mesh: &'a PolyMesh, root_path: &Path, tree_path: &str, options: &WriteOptions, instances: &mut InstanceTable<'a>) -> Result<()> {
 
     // Ensure the root path exists
     let _ = fs::create_dir_all(root_path).unwrap();
 
-    // Convert the mesh to a polymeta file
-    let metadata = mesh.to_poly_meta();
+    // Convert the mesh to a polymeta file, recording its size for tools that never read the geometry
+    let mut metadata = mesh.to_poly_meta();
+    metadata.bounds = mesh.bounds();
+
+    // A deferred placeholder that was never loaded has no geometry to write, so store it as an empty group that still reads back
+    if mesh.deferred_source.is_some() {
+        metadata.mesh_type = MeshType::Group;
+    }
+
+    // Point children that are already somewhere in the scene at the first copy, so instanced meshes are only written once.
+    // Siblings sharing a path would be written to the same directory, so the first one wins
+    let mut unique_children = Vec::new();
+    for (i, child) in mesh.children.iter().enumerate() {
+        let child_tree_path = format!("{}{}", tree_path, child.path);
+        if let Some(path) = instances.find(child.mesh.as_ref()) {
+            metadata.children[i].path = path;
+            metadata.children[i].path_mode = PathMode::RelativeToRoot;
+        } else if !unique_children.iter().any(|(other, _): &(&str, _)| *other == child.path) {
+            instances.insert(child.mesh.as_ref(), child_tree_path.clone());
+            unique_children.push((child.path.as_str(), child));
+        }
+    }
 
     // Write the metadata to file
     let meta_path = make_polymeta_file_path(root_path);
-    let meta_json = serde_json::to_string(&metadata).unwrap();
+    let meta_json = options.to_json(&metadata)?;
     let _ = fs::write(meta_path, meta_json).unwrap();
 
     // If there is geometry, write it too
-    if mesh.contains_geometry() {
+    if mesh.contains_geometry() && mesh.deferred_source.is_none() {
         let mesh_path = make_mesh_file_path(root_path);
-        let mesh_json = serde_json::to_string(&mesh.geometry).unwrap();
+        let mesh_json = match (&mesh.geometry, options.precision) {
+            (Some(geometry), Some(precision)) => options.to_json(&precision.round_mesh(geometry))?,
+            (geometry, _) => options.to_json(geometry)?
+        };
         let _ = fs::write(mesh_path, mesh_json).unwrap();
     }
 
-    // Write every child
-    for child in &mesh.children {
-
-        // Get the child's file path 
-        let child_path = make_child_file_path(root_path, &child.path);
-
-        // If this child has not been written, write it
-        if !written_children.contains(&child_path) {
-            let _ = write_unpacked_polymesh_recursive(child.mesh.as_ref(), &child_path, written_children).unwrap();
-
-            // Track the child to prevent overwrite
-            written_children.push(child_path);
-        }
+    // Write every unique child
+    for (path, child) in unique_children {
+        let child_path = make_child_file_path(root_path, path);
+        let _ = write_unpacked_polymesh_recursive(child.mesh.as_ref(), &child_path, &format!("{}{}", tree_path, path), options, instances).unwrap();
     }
 
     Ok(())
-}
\ No newline at end of file
+}
@@ -24,6 +24,8 @@ fn recurse_display(mesh: &pmf::PolyMesh, path: &str, level: usize, unique_only:
         name = name.blue();
     } else if mesh.mesh_type == pmf::MeshType::Geometry {
         name = name.green();
+    } else if mesh.mesh_type == pmf::MeshType::Locator {
+        name = name.yellow();
     } else {
         name = name.red();
     }
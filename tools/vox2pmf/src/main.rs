@@ -95,7 +95,7 @@ fn main() -> Result<()> {
             model_mesh.add_child(pmf::TransPolyMeshPtr {
                 path: (*known_voxels[&descriptor].path).to_string(),
                 mesh: known_voxels[&descriptor].mesh.clone(),
-                translation: Some(pmf::PolyVector {
+                transform: pmf::PolyTransform::from_translation(pmf::PolyVector {
                     x: voxel.x as f32,
                     y: voxel.z as f32,
                     z: (voxel.y as f32) * -1.0,
@@ -108,7 +108,7 @@ fn main() -> Result<()> {
         root_mesh.add_child(pmf::TransPolyMeshPtr {
             path: format!("/model_{}_gr", i).to_string(),
             mesh: Box::new(model_mesh),
-            translation: None // TODO: I think this needs to change??
+            transform: pmf::PolyTransform::identity() // TODO: I think this needs to change??
         })
 
     }